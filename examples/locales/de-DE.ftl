@@ -0,0 +1,7 @@
+count = Zählen
+reset = Zurücksetzen
+switch-locale = English
+clicks = { $count ->
+    [one] Einmal geklickt
+   *[other] { NUMBER($count) } Mal geklickt
+}
@@ -0,0 +1,7 @@
+count = Count
+reset = Reset
+switch-locale = Deutsch
+clicks = { $count ->
+    [one] Clicked once
+   *[other] Clicked { NUMBER($count) } times
+}
@@ -0,0 +1,85 @@
+use ui::{
+    app::{message::ApplicationMessage, Application, ApplicationDelegate},
+    localization::{localizer, LanguageIdentifier, LOCALE},
+    std::{flex::Row, label::Label, text_button::text_button},
+    user_interface::{
+        ui_state::UIState,
+        value::{Localized, Value},
+    },
+    window_request::WindowRequest,
+};
+
+pub struct AppDelegate;
+impl ApplicationDelegate for AppDelegate {
+    fn create_ui_state(&self) -> UIState {
+        let mut state = UIState::new();
+        state.register("counter_value", 0);
+        state.register(LOCALE, "en-US");
+        state
+    }
+
+    fn app_will_start(&self, app: &mut Application) {
+        {
+            let mut localizer = localizer();
+            localizer.set_diagnostics_handler(|error| eprintln!("{}", error));
+            for locale in ["en-US", "de-DE"] {
+                let locale: LanguageIdentifier = locale.parse().unwrap();
+                let path = format!(
+                    "{}/examples/locales/{}.ftl",
+                    env!("CARGO_MANIFEST_DIR"),
+                    locale
+                );
+                localizer
+                    .add_file(&locale, path)
+                    .expect("Loading catalog failed");
+            }
+        }
+
+        app.request_window(
+            WindowRequest::new(640, 240)
+                .with_title("Localization Example")
+                .with_ui(|_| {
+                    Row::new(|| {
+                        vec![
+                            text_button(Localized::new("count"), |message_ctx| {
+                                message_ctx.send(ApplicationMessage::new("count"))
+                            }),
+                            text_button(Localized::new("reset"), |message_ctx| {
+                                message_ctx.send(ApplicationMessage::new("reset"))
+                            }),
+                            text_button(Localized::new("switch-locale"), |message_ctx| {
+                                message_ctx.send(ApplicationMessage::new("switch_locale"))
+                            }),
+                            Label::new(
+                                Localized::new("clicks")
+                                    .with_arg("count", Value::Binding("counter_value".into())),
+                            )
+                            .into(),
+                        ]
+                    })
+                    .into()
+                }),
+        );
+    }
+
+    fn handle_message(&mut self, message: ApplicationMessage, state: &mut UIState) {
+        if message.target == "count" {
+            if let Some(old) = state.get("counter_value") {
+                state.set("counter_value", old.as_integer().unwrap() + 1);
+            }
+        } else if message.target == "reset" {
+            state.set("counter_value", 0);
+        } else if message.target == "switch_locale" {
+            let locale = if state[LOCALE].to_string() == "en-US" {
+                "de-DE"
+            } else {
+                "en-US"
+            };
+            state.set(LOCALE, locale);
+        }
+    }
+}
+
+fn main() {
+    Application::start(AppDelegate {});
+}
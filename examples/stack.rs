@@ -0,0 +1,58 @@
+use ui::{
+    app::{message::ApplicationMessage, Application, ApplicationDelegate},
+    canvas::color::Color32f,
+    std::{
+        container::Container,
+        label::Label,
+        stack::{Positioned, Stack},
+        text_button::text_button,
+    },
+    user_interface::ui_state::UIState,
+    widget::style::Alignment,
+    window_request::WindowRequest,
+};
+
+pub struct AppDelegate;
+impl ApplicationDelegate for AppDelegate {
+    fn create_ui_state(&self) -> UIState {
+        UIState::new()
+    }
+
+    fn app_will_start(&self, app: &mut Application) {
+        app.request_window(
+            WindowRequest::new(480, 240)
+                .with_title("Stack Example")
+                .with_ui(|_| {
+                    Stack::new(|| {
+                        vec![
+                            Container::new(|_| Label::new("Background").into())
+                                .with_color(Color32f::new_grey(0.2).into())
+                                .into(),
+                            Positioned::new(|_| {
+                                Container::new(|_| Label::new("3").into())
+                                    .with_color(Color32f::new_rgb(0.8, 0.1, 0.1).into())
+                                    .into()
+                            })
+                            .with_top(8.0)
+                            .with_right(8.0)
+                            .with_width(32.0)
+                            .with_height(32.0)
+                            .into(),
+                            Positioned::new(|_| text_button("+", |_| {}))
+                                .with_right(16.0)
+                                .with_bottom(16.0)
+                                .into(),
+                        ]
+                    })
+                    .with_alignment(Alignment::CENTER)
+                    .into()
+                }),
+        );
+    }
+
+    fn handle_message(&mut self, _: ApplicationMessage, _: &mut UIState) {}
+}
+
+fn main() {
+    Application::start(AppDelegate {});
+}
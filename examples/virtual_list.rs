@@ -0,0 +1,45 @@
+use ui::{
+    app::{message::ApplicationMessage, Application, ApplicationDelegate},
+    canvas::color::Color32f,
+    std::{
+        container::Container,
+        label::Label,
+        viewport::Scrollable,
+        virtual_list::{RowHeight, VirtualList},
+    },
+    user_interface::ui_state::UIState,
+    window_request::WindowRequest,
+};
+
+pub struct AppDelegate;
+impl ApplicationDelegate for AppDelegate {
+    fn create_ui_state(&self) -> UIState {
+        UIState::new()
+    }
+
+    fn app_will_start(&self, app: &mut Application) {
+        app.request_window(
+            WindowRequest::new(480, 240)
+                .with_title("Virtual List Example")
+                .with_ui(|_| {
+                    Scrollable::new(|_| {
+                        VirtualList::new(100_000, RowHeight::Fixed(30.0), |i| {
+                            let color = if i % 2 == 0 { 0.25 } else { 0.2 };
+                            Container::new(move |_| Label::new(format!("Line {}", i)).into())
+                                .with_height(30.0)
+                                .with_color(Color32f::new_grey(color).into())
+                                .into()
+                        })
+                        .into()
+                    })
+                    .into()
+                }),
+        );
+    }
+
+    fn handle_message(&mut self, _message: ApplicationMessage, _state: &mut UIState) {}
+}
+
+fn main() {
+    Application::start(AppDelegate {});
+}
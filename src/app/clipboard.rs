@@ -0,0 +1,75 @@
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+static CLIPBOARD: OnceLock<Mutex<Box<dyn Clipboard>>> = OnceLock::new();
+
+/// The clipboard that is shared by all windows, the system clipboard unless `set_clipboard` replaced it.
+pub fn clipboard() -> MutexGuard<'static, Box<dyn Clipboard>> {
+    CLIPBOARD
+        .get_or_init(|| Mutex::new(Box::new(SystemClipboard::new())))
+        .lock()
+        .expect("Clipboard lock poisoned")
+}
+
+/// Replaces the shared clipboard, for example with an in memory one on platforms without a clipboard.
+pub fn set_clipboard(new_clipboard: impl Clipboard + 'static) {
+    *clipboard() = Box::new(new_clipboard);
+}
+
+pub trait Clipboard: Send {
+    fn text(&mut self) -> Option<String>;
+    fn set_text(&mut self, text: &str);
+}
+
+/// The clipboard of the operating system. It is opened on first use, if that fails it stays empty.
+pub struct SystemClipboard {
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    pub fn new() -> Self {
+        Self { clipboard: None }
+    }
+
+    fn clipboard(&mut self) -> Option<&mut arboard::Clipboard> {
+        if self.clipboard.is_none() {
+            self.clipboard = arboard::Clipboard::new().ok();
+        }
+
+        self.clipboard.as_mut()
+    }
+}
+
+impl Default for SystemClipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn text(&mut self) -> Option<String> {
+        self.clipboard()?.get_text().ok()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        if let Some(clipboard) = self.clipboard() {
+            // A clipboard that refuses the text is not worth failing over, the copy is simply lost
+            let _ = clipboard.set_text(text.to_string());
+        }
+    }
+}
+
+/// A clipboard that only lives inside of the application.
+#[derive(Default)]
+pub struct LocalClipboard {
+    text: Option<String>,
+}
+
+impl Clipboard for LocalClipboard {
+    fn text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.text = Some(text.to_string());
+    }
+}
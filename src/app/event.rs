@@ -1,7 +1,10 @@
 use crate::{
-    animation::animation_event::AnimationEvent, geo::Point, mouse_event::MouseEventData,
+    animation::animation_event::AnimationEvent,
+    geo::{Point, Transform},
+    mouse_event::MouseEventData,
     tree::ElementId,
 };
+use winit::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode};
 
 pub enum ApplicationEvent {
     Mouse(MouseEvent),
@@ -38,22 +41,50 @@ impl MouseEvent {
         }
     }
 
-    pub fn to_local(&self, position: &Point) -> MouseEvent {
+    /// Maps the position with the transform from window to local coordinates.
+    pub fn to_local(&self, to_local: &Transform) -> MouseEvent {
         match self {
-            Self::MouseMove(event) => Self::MouseMove(event.to_local(position)),
-            Self::MouseEnter(event) => Self::MouseEnter(event.to_local(position)),
-            Self::MouseLeave(event) => Self::MouseLeave(event.to_local(position)),
-            Self::MouseUp(event) => Self::MouseUp(event.to_local(position)),
-            Self::MouseDown(event) => Self::MouseDown(event.to_local(position)),
-            Self::MouseDrag(event) => Self::MouseDrag(event.to_local(position)),
-            Self::MouseDragStart(event) => Self::MouseDragStart(event.to_local(position)),
-            Self::MouseDragEnd(event) => Self::MouseDragEnd(event.to_local(position)),
-            Self::MouseScroll(event) => Self::MouseScroll(event.to_local(position)),
+            Self::MouseMove(event) => Self::MouseMove(event.to_local(to_local)),
+            Self::MouseEnter(event) => Self::MouseEnter(event.to_local(to_local)),
+            Self::MouseLeave(event) => Self::MouseLeave(event.to_local(to_local)),
+            Self::MouseUp(event) => Self::MouseUp(event.to_local(to_local)),
+            Self::MouseDown(event) => Self::MouseDown(event.to_local(to_local)),
+            Self::MouseDrag(event) => Self::MouseDrag(event.to_local(to_local)),
+            Self::MouseDragStart(event) => Self::MouseDragStart(event.to_local(to_local)),
+            Self::MouseDragEnd(event) => Self::MouseDragEnd(event.to_local(to_local)),
+            Self::MouseScroll(event) => Self::MouseScroll(event.to_local(to_local)),
         }
     }
 }
 
 pub enum KeyEvent {
-    Input(winit::event::KeyboardInput),
+    /// A key was pressed or released, with the modifiers that were held at the time.
+    Input(KeyboardInput, ModifiersState),
     Char(char),
 }
+
+impl KeyEvent {
+    /// Whether the key was pressed together with the shortcut modifier, command on macOS and control elsewhere.
+    pub fn is_shortcut(&self, key: VirtualKeyCode) -> bool {
+        match self {
+            Self::Input(input, modifiers) => {
+                let modifier = if cfg!(target_os = "macos") {
+                    modifiers.logo()
+                } else {
+                    modifiers.ctrl()
+                };
+                modifier
+                    && input.state == ElementState::Pressed
+                    && input.virtual_keycode == Some(key)
+            }
+            Self::Char(_) => false,
+        }
+    }
+
+    pub fn is_shift_down(&self) -> bool {
+        match self {
+            Self::Input(_, modifiers) => modifiers.shift(),
+            Self::Char(_) => false,
+        }
+    }
+}
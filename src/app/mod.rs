@@ -1,4 +1,5 @@
 mod application_delegate;
+pub mod clipboard;
 pub mod event;
 
 pub use application_delegate::ApplicationDelegate;
@@ -28,13 +29,15 @@ use std::{
 };
 use winit::{
     dpi::{LogicalSize, PhysicalPosition},
-    event::{DeviceId, ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent},
+    event::{
+        DeviceId, ElementState, Event, ModifiersState, MouseButton, MouseScrollDelta, WindowEvent,
+    },
     event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
     window::{Window, WindowBuilder, WindowId},
 };
 
 use self::{
-    event::ApplicationEvent,
+    event::{ApplicationEvent, KeyEvent},
     message::ApplicationMessage,
     render_thread::{
         MergeResult, RenderSendersAndReceivers, RenderThread, RenderThreadMessage, StateUpdate,
@@ -165,6 +168,7 @@ pub struct Application {
     user_interfaces: HashMap<WindowId, UserInterface>,
     painter_trees: HashMap<WindowId, Sender<TreePainterMessage>>,
     windows: HashMap<WindowId, Window>,
+    modifiers: ModifiersState,
     pub io: RenderSendersAndReceivers,
     _render_thread_handle: JoinHandle<()>,
 }
@@ -189,6 +193,7 @@ impl Application {
             painter_trees: HashMap::new(),
             user_interfaces: HashMap::new(),
             windows: HashMap::new(),
+            modifiers: ModifiersState::empty(),
             io,
             _render_thread_handle: render_thread.start(),
         };
@@ -217,6 +222,18 @@ impl Application {
         }
     }
 
+    fn handle_key_input(&mut self, window_id: &WindowId, event: KeyEvent) {
+        if let Some(ui) = self.user_interfaces.get_mut(window_id) {
+            let mut message_ctx = ApplicationCtx::new(self.senders.clone());
+            ui.key_event(
+                &event,
+                &mut message_ctx,
+                &self.ui_state,
+                self.senders.clone(),
+            );
+        }
+    }
+
     fn handle_mouse_input(
         &mut self,
         window_id: &WindowId,
@@ -231,6 +248,7 @@ impl Application {
                 if let Some(ui) = self.user_interfaces.get_mut(window_id) {
                     ui.mouse_down(
                         *window_id,
+                        self.modifiers,
                         &mut message_ctx,
                         &self.ui_state,
                         event_response,
@@ -242,6 +260,7 @@ impl Application {
                 if let Some(ui) = self.user_interfaces.get_mut(window_id) {
                     ui.mouse_up(
                         *window_id,
+                        self.modifiers,
                         &mut message_ctx,
                         &self.ui_state,
                         event_response,
@@ -265,6 +284,7 @@ impl Application {
         if let Some(ui) = self.user_interfaces.get_mut(window_id) {
             ui.mouse_move(
                 *window_id,
+                self.modifiers,
                 position,
                 &mut message_ctx,
                 &self.ui_state,
@@ -289,6 +309,7 @@ impl Application {
         if let Some(ui) = self.user_interfaces.get_mut(window_id) {
             ui.mouse_scroll(
                 *window_id,
+                self.modifiers,
                 scroll,
                 &mut message_ctx,
                 &self.ui_state,
@@ -331,16 +352,16 @@ impl Application {
             WindowEvent::DroppedFile(_) => todo!(),
             WindowEvent::HoveredFile(_) => todo!(),
             WindowEvent::HoveredFileCancelled => todo!(),
-            WindowEvent::ReceivedCharacter(_) => todo!(),
+            WindowEvent::ReceivedCharacter(character) => {
+                self.handle_key_input(window_id, KeyEvent::Char(*character))
+            }
             WindowEvent::Focused(state) => {
                 self.handle_focus_change(window_id, *state, event_response);
             }
-            WindowEvent::KeyboardInput {
-                device_id,
-                input,
-                is_synthetic,
-            } => (), //todo!(),
-            WindowEvent::ModifiersChanged(_) => (), //todo!(),
+            WindowEvent::KeyboardInput { input, .. } => {
+                self.handle_key_input(window_id, KeyEvent::Input(*input, self.modifiers))
+            }
+            WindowEvent::ModifiersChanged(modifiers) => self.modifiers = *modifiers,
             WindowEvent::Ime(_) => todo!(),
             WindowEvent::CursorMoved {
                 device_id,
@@ -572,7 +593,9 @@ impl Application {
                 let mut ui = UserInterface::new(
                     root,
                     Size::new(request.width as f32, request.height as f32),
-                );
+                )
+                .with_text_direction(request.text_direction)
+                .with_dpi(window.scale_factor() as f32);
                 let (widget_tree, build_result) = ui.build(&mut self.ui_state);
                 for (element_id, bindings) in build_result.binds {
                     for bind in bindings {
@@ -1,6 +1,7 @@
 use std::ops::{Add, Mul};
 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Color8u {
     pub r: u8,
     pub g: u8,
@@ -0,0 +1,732 @@
+use std::fmt;
+
+use crate::{
+    geo::{Point, Rect, Size, Transform},
+    widget::style::TextDirection,
+};
+
+use super::{
+    color::Color,
+    font::Font,
+    image::{Image, Sampling},
+    layer::Layer,
+    nine_patch::Lattice,
+    paint::Paint,
+    paragraph::Paragraph,
+    path::Path,
+    shadow::Shadow,
+    svg::Svg,
+    text::{Text, TextAlignment},
+    Canvas,
+};
+
+/// One call on a `Canvas`, with everything it was called with.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DrawCommand {
+    Clear(Color),
+    Save,
+    SaveLayer(Layer),
+    Restore,
+    Translate(Point),
+    Scale(Size),
+    Rotate(f32),
+    Skew(f32, f32),
+    Concat(Transform),
+    ClipRect(Rect),
+    ClipRoundedRect {
+        rect: Rect,
+        rx: f32,
+        ry: f32,
+    },
+    ClipPath(Path),
+    DrawRect {
+        rect: Rect,
+        paint: Paint,
+    },
+    DrawRoundedRect {
+        rect: Rect,
+        rx: f32,
+        ry: f32,
+        paint: Paint,
+    },
+    DrawCircle {
+        center: Point,
+        radius: f32,
+        paint: Paint,
+    },
+    DrawOval {
+        rect: Rect,
+        paint: Paint,
+    },
+    DrawArc {
+        oval: Rect,
+        start_angle: f32,
+        sweep_angle: f32,
+        use_center: bool,
+        paint: Paint,
+    },
+    DrawPath {
+        path: Path,
+        paint: Paint,
+    },
+    DrawShadow {
+        path: Path,
+        shadow: Shadow,
+    },
+    DrawLine {
+        from: Point,
+        to: Point,
+        paint: Paint,
+    },
+    DrawPolyline {
+        points: Vec<Point>,
+        paint: Paint,
+    },
+    DrawImage {
+        image: Image,
+        position: Point,
+        sampling: Sampling,
+        paint: Option<Paint>,
+    },
+    DrawImageRect {
+        image: Image,
+        src: Option<Rect>,
+        dst: Rect,
+        sampling: Sampling,
+        paint: Option<Paint>,
+    },
+    DrawImageNine {
+        image: Image,
+        center: Rect,
+        dst: Rect,
+        sampling: Sampling,
+        paint: Option<Paint>,
+    },
+    DrawImageLattice {
+        image: Image,
+        lattice: Lattice,
+        dst: Rect,
+        sampling: Sampling,
+        paint: Option<Paint>,
+    },
+    DrawSvg {
+        svg: Svg,
+        dst: Rect,
+        paint: Option<Paint>,
+    },
+    /// Both `draw_text` and `draw_string` are recorded as the string, its font and the direction
+    /// it was shaped with, the text is shaped again when it is replayed.
+    DrawText {
+        text: String,
+        font: Font,
+        text_direction: TextDirection,
+        rect: Rect,
+        alignment: TextAlignment,
+        paint: Paint,
+    },
+    /// The paragraph is shared with the painter that drew it. When the painter has laid it out
+    /// for another width since, a copy is laid out for the width it had when it was recorded.
+    DrawParagraph {
+        position: Point,
+        paragraph: Paragraph,
+        width: Option<f32>,
+    },
+}
+
+impl DrawCommand {
+    /// Makes the call again on the canvas.
+    pub fn replay(&self, canvas: &mut dyn Canvas) {
+        match self {
+            DrawCommand::Clear(color) => canvas.clear(color),
+            DrawCommand::Save => canvas.save(),
+            DrawCommand::SaveLayer(layer) => canvas.save_layer(layer),
+            DrawCommand::Restore => canvas.restore(),
+            DrawCommand::Translate(point) => canvas.translate(point),
+            DrawCommand::Scale(size) => canvas.scale(size),
+            DrawCommand::Rotate(degrees) => canvas.rotate(*degrees),
+            DrawCommand::Skew(x, y) => canvas.skew(*x, *y),
+            DrawCommand::Concat(transform) => canvas.concat(transform),
+            DrawCommand::ClipRect(rect) => canvas.clip_rect(rect),
+            DrawCommand::ClipRoundedRect { rect, rx, ry } => {
+                canvas.clip_rounded_rect(rect, *rx, *ry)
+            }
+            DrawCommand::ClipPath(path) => canvas.clip_path(path),
+            DrawCommand::DrawRect { rect, paint } => canvas.draw_rect(rect, paint),
+            DrawCommand::DrawRoundedRect {
+                rect,
+                rx,
+                ry,
+                paint,
+            } => canvas.draw_rounded_rect(rect, *rx, *ry, paint),
+            DrawCommand::DrawCircle {
+                center,
+                radius,
+                paint,
+            } => canvas.draw_circle(center, *radius, paint),
+            DrawCommand::DrawOval { rect, paint } => canvas.draw_oval(rect, paint),
+            DrawCommand::DrawArc {
+                oval,
+                start_angle,
+                sweep_angle,
+                use_center,
+                paint,
+            } => canvas.draw_arc(oval, *start_angle, *sweep_angle, *use_center, paint),
+            DrawCommand::DrawPath { path, paint } => canvas.draw_path(path, paint),
+            DrawCommand::DrawShadow { path, shadow } => canvas.draw_shadow(path, shadow),
+            DrawCommand::DrawLine { from, to, paint } => canvas.draw_line(from, to, paint),
+            DrawCommand::DrawPolyline { points, paint } => canvas.draw_polyline(points, paint),
+            DrawCommand::DrawImage {
+                image,
+                position,
+                sampling,
+                paint,
+            } => canvas.draw_image(image, position, *sampling, paint.as_ref()),
+            DrawCommand::DrawImageRect {
+                image,
+                src,
+                dst,
+                sampling,
+                paint,
+            } => canvas.draw_image_rect(image, src.as_ref(), dst, *sampling, paint.as_ref()),
+            DrawCommand::DrawImageNine {
+                image,
+                center,
+                dst,
+                sampling,
+                paint,
+            } => canvas.draw_image_nine(image, center, dst, *sampling, paint.as_ref()),
+            DrawCommand::DrawImageLattice {
+                image,
+                lattice,
+                dst,
+                sampling,
+                paint,
+            } => canvas.draw_image_lattice(image, lattice, dst, *sampling, paint.as_ref()),
+            DrawCommand::DrawSvg { svg, dst, paint } => canvas.draw_svg(svg, dst, paint.as_ref()),
+            DrawCommand::DrawText {
+                text,
+                font,
+                text_direction,
+                rect,
+                alignment,
+                paint,
+            } => {
+                let text = Text::new(text, font.clone(), *text_direction);
+                canvas.draw_text(&text, rect, *alignment, paint)
+            }
+            DrawCommand::DrawParagraph {
+                position,
+                paragraph,
+                width,
+            } => match width {
+                Some(width) if paragraph.layout_width() != Some(*width) => {
+                    canvas.draw_paragraph(position, &paragraph.copy_with_width(*width))
+                }
+                _ => canvas.draw_paragraph(position, paragraph),
+            },
+        }
+    }
+}
+
+/// The calls a `RecordingCanvas` recorded, in order. It can be replayed onto any other canvas,
+/// compared in tests, and printed as a readable dump with one call per line.
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DisplayList {
+    commands: Vec<DrawCommand>,
+}
+
+impl DisplayList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// The commands that draw, without the saves, restores, transforms and clips around them.
+    pub fn draws(&self) -> impl Iterator<Item = &DrawCommand> {
+        self.commands.iter().filter(|command| {
+            !matches!(
+                command,
+                DrawCommand::Save
+                    | DrawCommand::SaveLayer(_)
+                    | DrawCommand::Restore
+                    | DrawCommand::Translate(_)
+                    | DrawCommand::Scale(_)
+                    | DrawCommand::Rotate(_)
+                    | DrawCommand::Skew(_, _)
+                    | DrawCommand::Concat(_)
+                    | DrawCommand::ClipRect(_)
+                    | DrawCommand::ClipRoundedRect { .. }
+                    | DrawCommand::ClipPath(_)
+            )
+        })
+    }
+
+    /// Makes all the recorded calls again on the canvas.
+    pub fn replay(&self, canvas: &mut dyn Canvas) {
+        for command in &self.commands {
+            command.replay(canvas);
+        }
+    }
+}
+
+impl FromIterator<DrawCommand> for DisplayList {
+    fn from_iter<T: IntoIterator<Item = DrawCommand>>(iter: T) -> Self {
+        Self {
+            commands: iter.into_iter().collect(),
+        }
+    }
+}
+
+// The calls between a save and its restore are indented.
+impl fmt::Display for DisplayList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut depth = 0;
+        for command in &self.commands {
+            if let DrawCommand::Restore = command {
+                depth = depth.saturating_sub(1);
+            }
+            writeln!(f, "{}{}", "  ".repeat(depth), command)?;
+            if let DrawCommand::Save | DrawCommand::SaveLayer(_) = command {
+                depth += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DrawCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrawCommand::Clear(color) => write!(f, "clear {}", DumpColor(color)),
+            DrawCommand::Save => write!(f, "save"),
+            DrawCommand::SaveLayer(layer) => write!(f, "save_layer {:?}", layer),
+            DrawCommand::Restore => write!(f, "restore"),
+            DrawCommand::Translate(point) => write!(f, "translate {}", DumpPoint(point)),
+            DrawCommand::Scale(size) => write!(f, "scale {} {}", size.width, size.height),
+            DrawCommand::Rotate(degrees) => write!(f, "rotate {}", degrees),
+            DrawCommand::Skew(x, y) => write!(f, "skew {} {}", x, y),
+            DrawCommand::Concat(transform) => write!(f, "concat {:?}", transform),
+            DrawCommand::ClipRect(rect) => write!(f, "clip_rect {}", DumpRect(rect)),
+            DrawCommand::ClipRoundedRect { rect, rx, ry } => {
+                write!(f, "clip_rounded_rect {} {} {}", DumpRect(rect), rx, ry)
+            }
+            DrawCommand::ClipPath(path) => write!(f, "clip_path {:?}", path),
+            DrawCommand::DrawRect { rect, paint } => {
+                write!(f, "draw_rect {} {}", DumpRect(rect), DumpPaint(paint))
+            }
+            DrawCommand::DrawRoundedRect {
+                rect,
+                rx,
+                ry,
+                paint,
+            } => write!(
+                f,
+                "draw_rounded_rect {} {} {} {}",
+                DumpRect(rect),
+                rx,
+                ry,
+                DumpPaint(paint)
+            ),
+            DrawCommand::DrawCircle {
+                center,
+                radius,
+                paint,
+            } => write!(
+                f,
+                "draw_circle {} {} {}",
+                DumpPoint(center),
+                radius,
+                DumpPaint(paint)
+            ),
+            DrawCommand::DrawOval { rect, paint } => {
+                write!(f, "draw_oval {} {}", DumpRect(rect), DumpPaint(paint))
+            }
+            DrawCommand::DrawArc {
+                oval,
+                start_angle,
+                sweep_angle,
+                use_center,
+                paint,
+            } => write!(
+                f,
+                "draw_arc {} {} {} {} {}",
+                DumpRect(oval),
+                start_angle,
+                sweep_angle,
+                use_center,
+                DumpPaint(paint)
+            ),
+            DrawCommand::DrawPath { path, paint } => {
+                write!(f, "draw_path {:?} {}", path, DumpPaint(paint))
+            }
+            DrawCommand::DrawShadow { path, shadow } => {
+                write!(f, "draw_shadow {:?} {:?}", path, shadow)
+            }
+            DrawCommand::DrawLine { from, to, paint } => write!(
+                f,
+                "draw_line {} {} {}",
+                DumpPoint(from),
+                DumpPoint(to),
+                DumpPaint(paint)
+            ),
+            DrawCommand::DrawPolyline { points, paint } => {
+                write!(f, "draw_polyline")?;
+                for point in points {
+                    write!(f, " {}", DumpPoint(point))?;
+                }
+                write!(f, " {}", DumpPaint(paint))
+            }
+            DrawCommand::DrawImage {
+                image,
+                position,
+                sampling,
+                paint,
+            } => {
+                write!(
+                    f,
+                    "draw_image {}x{} {} {:?}",
+                    image.width(),
+                    image.height(),
+                    DumpPoint(position),
+                    sampling
+                )?;
+                write!(f, "{}", DumpOptionalPaint(paint))
+            }
+            DrawCommand::DrawImageRect {
+                image,
+                src,
+                dst,
+                sampling,
+                paint,
+            } => {
+                write!(f, "draw_image_rect {}x{}", image.width(), image.height())?;
+                if let Some(src) = src {
+                    write!(f, " {}", DumpRect(src))?;
+                }
+                write!(f, " {} {:?}", DumpRect(dst), sampling)?;
+                write!(f, "{}", DumpOptionalPaint(paint))
+            }
+            DrawCommand::DrawImageNine {
+                image,
+                center,
+                dst,
+                sampling,
+                paint,
+            } => {
+                write!(
+                    f,
+                    "draw_image_nine {}x{} {} {} {:?}",
+                    image.width(),
+                    image.height(),
+                    DumpRect(center),
+                    DumpRect(dst),
+                    sampling
+                )?;
+                write!(f, "{}", DumpOptionalPaint(paint))
+            }
+            DrawCommand::DrawImageLattice {
+                image,
+                lattice,
+                dst,
+                sampling,
+                paint,
+            } => {
+                write!(
+                    f,
+                    "draw_image_lattice {}x{} {:?} {:?} {} {:?}",
+                    image.width(),
+                    image.height(),
+                    lattice.x_divs(),
+                    lattice.y_divs(),
+                    DumpRect(dst),
+                    sampling
+                )?;
+                write!(f, "{}", DumpOptionalPaint(paint))
+            }
+            DrawCommand::DrawSvg { svg, dst, paint } => {
+                let size = svg.size();
+                write!(
+                    f,
+                    "draw_svg {}x{} {}",
+                    size.width,
+                    size.height,
+                    DumpRect(dst)
+                )?;
+                write!(f, "{}", DumpOptionalPaint(paint))
+            }
+            DrawCommand::DrawText {
+                text,
+                font,
+                text_direction,
+                rect,
+                paint,
+                ..
+            } => {
+                write!(
+                    f,
+                    "draw_text {:?} {} {} {} {}",
+                    text,
+                    font.typeface(),
+                    font.size(),
+                    DumpRect(rect),
+                    DumpPaint(paint)
+                )?;
+                if text_direction.is_right_to_left() {
+                    write!(f, " {:?}", text_direction)?;
+                }
+                Ok(())
+            }
+            DrawCommand::DrawParagraph {
+                position,
+                paragraph,
+                width,
+            } => {
+                write!(
+                    f,
+                    "draw_paragraph {:?} {}",
+                    paragraph.text(),
+                    DumpPoint(position)
+                )?;
+                if let Some(width) = width {
+                    write!(f, " width {}", width)?;
+                }
+                for run in paragraph.runs() {
+                    write!(f, " {}", run)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+struct DumpPoint<'a>(&'a Point);
+
+impl fmt::Display for DumpPoint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.0.x, self.0.y)
+    }
+}
+
+struct DumpRect<'a>(&'a Rect);
+
+impl fmt::Display for DumpRect<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.0.size();
+        write!(
+            f,
+            "({}, {} {}x{})",
+            self.0.left(),
+            self.0.top(),
+            size.width,
+            size.height
+        )
+    }
+}
+
+struct DumpColor<'a>(&'a Color);
+
+impl fmt::Display for DumpColor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{:02x}{:02x}{:02x}{:02x}",
+            self.0.r, self.0.g, self.0.b, self.0.a
+        )
+    }
+}
+
+// The color, and every other field of the paint that differs from a plain fill.
+struct DumpPaint<'a>(&'a Paint);
+
+impl fmt::Display for DumpPaint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let paint = self.0;
+        let fill = Paint::new(*paint.color());
+        write!(f, "{}", DumpColor(paint.color()))?;
+        if paint.style() != fill.style() {
+            write!(f, " {:?}", paint.style())?;
+        }
+        if paint.stroke_width() != fill.stroke_width() {
+            write!(f, " width {}", paint.stroke_width())?;
+        }
+        if paint.stroke_cap() != fill.stroke_cap() {
+            write!(f, " cap {:?}", paint.stroke_cap())?;
+        }
+        if paint.stroke_join() != fill.stroke_join() {
+            write!(f, " join {:?}", paint.stroke_join())?;
+        }
+        if paint.miter_limit() != fill.miter_limit() {
+            write!(f, " miter {}", paint.miter_limit())?;
+        }
+        if let Some(dash) = paint.dash() {
+            write!(f, " dash {:?} {}", dash.intervals(), dash.phase())?;
+        }
+        if paint.anti_alias() != fill.anti_alias() {
+            write!(f, " anti_alias {}", paint.anti_alias())?;
+        }
+        if let Some(shader) = paint.shader() {
+            write!(f, " {:?}", shader)?;
+        }
+        if paint.blend_mode() != fill.blend_mode() {
+            write!(f, " {:?}", paint.blend_mode())?;
+        }
+        Ok(())
+    }
+}
+
+// The paint of calls that draw without one unless it is given, after a space when there is one.
+struct DumpOptionalPaint<'a>(&'a Option<Paint>);
+
+impl fmt::Display for DumpOptionalPaint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(paint) => write!(f, " {}", DumpPaint(paint)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisplayList, DrawCommand};
+    use crate::{
+        canvas::{
+            color::Color32f,
+            font::Font,
+            image::{Image, Sampling},
+            paint::{Dash, Paint, StrokeCap, StrokeJoin},
+            paragraph::{Paragraph, ParagraphStyle},
+            recording_canvas::RecordingCanvas,
+            Canvas,
+        },
+        geo::{Point, Rect, Size},
+    };
+
+    fn red() -> Paint {
+        Paint::new(Color32f::new_rgb(1.0, 0.0, 0.0))
+    }
+
+    fn record() -> DisplayList {
+        let mut canvas = RecordingCanvas::new();
+        canvas.save();
+        canvas.translate(&Point::new(10.0, 20.0));
+        canvas.draw_rect(&Rect::new_from_size(Size::new(30.0, 40.0)), &red());
+        canvas.restore();
+        canvas.into_display_list()
+    }
+
+    #[test]
+    fn records_calls_in_order() {
+        let list = record();
+        assert_eq!(
+            list.commands(),
+            &[
+                DrawCommand::Save,
+                DrawCommand::Translate(Point::new(10.0, 20.0)),
+                DrawCommand::DrawRect {
+                    rect: Rect::new_from_size(Size::new(30.0, 40.0)),
+                    paint: red(),
+                },
+                DrawCommand::Restore,
+            ]
+        );
+        assert_eq!(list.draws().count(), 1);
+    }
+
+    #[test]
+    fn recordings_of_the_same_calls_are_equal() {
+        assert_eq!(record(), record());
+
+        let mut other = record();
+        other.push(DrawCommand::Rotate(90.0));
+        assert_ne!(record(), other);
+    }
+
+    #[test]
+    fn dump_indents_calls_between_save_and_restore() {
+        assert_eq!(
+            record().to_string(),
+            "save\n  translate (10, 20)\n  draw_rect (0, 0 30x40) #ff0000ff\nrestore\n"
+        );
+    }
+
+    #[test]
+    fn dump_prints_the_fields_that_differ_from_a_fill() {
+        let paint = Paint::new_stroke(Color32f::new_rgb(1.0, 0.0, 0.0), 2.0)
+            .with_stroke_cap(StrokeCap::Round)
+            .with_stroke_join(StrokeJoin::Bevel)
+            .with_miter_limit(2.0)
+            .with_dash(Dash::new(vec![4.0, 2.0]).unwrap().with_phase(1.0))
+            .with_anti_alias(false);
+        let command = DrawCommand::DrawLine {
+            from: Point::new(0.0, 0.0),
+            to: Point::new(10.0, 0.0),
+            paint,
+        };
+        assert_eq!(
+            command.to_string(),
+            "draw_line (0, 0) (10, 0) #ff0000ff Stroke width 2 cap Round join Bevel miter 2 \
+             dash [4.0, 2.0] 1 anti_alias false"
+        );
+    }
+
+    #[test]
+    fn dump_prints_the_paint_of_images() {
+        let image = Image::from_rgba(2, 1, &[255; 8]).unwrap();
+        let command = |paint| DrawCommand::DrawImage {
+            image: image.clone(),
+            position: Point::new(0.0, 0.0),
+            sampling: Sampling::default(),
+            paint,
+        };
+        assert!(command(Some(red())).to_string().ends_with(" #ff0000ff"));
+        assert!(!command(None).to_string().contains('#'));
+    }
+
+    #[test]
+    fn paragraphs_differ_by_the_styles_of_their_runs() {
+        let font = Font::default();
+        let style = ParagraphStyle::new();
+        let plain = Paragraph::new("text", font.clone(), style.clone());
+        let mut red_style = Paragraph::text_style(&font, &style);
+        red_style.set_color(skia_safe::Color::RED);
+        let red = Paragraph::new_with_runs(&[("text", red_style)], font, style);
+        assert_ne!(plain, red);
+
+        let command = |paragraph| DrawCommand::DrawParagraph {
+            position: Point::new(0.0, 0.0),
+            paragraph,
+            width: None,
+        };
+        assert!(command(red).to_string().contains(" #ff0000ff]"));
+        assert!(!command(plain).to_string().contains(" #ff0000ff]"));
+    }
+
+    #[test]
+    fn replay_makes_the_same_calls() {
+        let mut canvas = RecordingCanvas::new();
+        record().replay(&mut canvas);
+        assert_eq!(canvas.into_display_list(), record());
+    }
+}
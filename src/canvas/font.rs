@@ -1,3 +1,7 @@
+use std::hash::{Hash, Hasher};
+
+use super::font_manager::font_manager;
+
 #[derive(Clone, Copy)]
 pub enum FontStyle {
     Normal,
@@ -5,9 +9,48 @@ pub enum FontStyle {
     Bold,
 }
 
+/// Thickness of a font, from 100 (thin) to 900 (black).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const NORMAL: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const BLACK: FontWeight = FontWeight(900);
+}
+
+/// How condensed or expanded a font is, from 1 (ultra condensed) to 9 (ultra expanded).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FontWidth(pub u16);
+
+impl FontWidth {
+    pub const CONDENSED: FontWidth = FontWidth(3);
+    pub const SEMI_CONDENSED: FontWidth = FontWidth(4);
+    pub const NORMAL: FontWidth = FontWidth(5);
+    pub const SEMI_EXPANDED: FontWidth = FontWidth(6);
+    pub const EXPANDED: FontWidth = FontWidth(7);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FontSlant {
+    Upright,
+    Italic,
+    Oblique,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Font {
     typeface: String,
-    style: FontStyle,
+    weight: FontWeight,
+    width: FontWidth,
+    slant: FontSlant,
     size: f32,
 }
 
@@ -15,11 +58,43 @@ impl Font {
     pub fn new(typeface: &str, size: f32) -> Self {
         Self {
             typeface: typeface.to_string(),
-            style: FontStyle::Normal,
+            weight: FontWeight::NORMAL,
+            width: FontWidth::NORMAL,
+            slant: FontSlant::Upright,
             size,
         }
     }
 
+    pub fn with_style(self, style: FontStyle) -> Self {
+        match style {
+            FontStyle::Normal => self
+                .with_weight(FontWeight::NORMAL)
+                .with_slant(FontSlant::Upright),
+            FontStyle::Italic => self.with_slant(FontSlant::Italic),
+            FontStyle::Bold => self.with_weight(FontWeight::BOLD),
+        }
+    }
+
+    pub fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_width(mut self, width: FontWidth) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_slant(mut self, slant: FontSlant) -> Self {
+        self.slant = slant;
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
     pub fn size(&self) -> f32 {
         self.size
     }
@@ -28,7 +103,55 @@ impl Font {
         &self.typeface
     }
 
+    pub fn weight(&self) -> FontWeight {
+        self.weight
+    }
+
+    pub fn width(&self) -> FontWidth {
+        self.width
+    }
+
+    pub fn slant(&self) -> FontSlant {
+        self.slant
+    }
+
     pub fn style(&self) -> FontStyle {
-        self.style
+        if self.slant != FontSlant::Upright {
+            FontStyle::Italic
+        } else if self.weight >= FontWeight::BOLD {
+            FontStyle::Bold
+        } else {
+            FontStyle::Normal
+        }
+    }
+}
+
+/// The default font of the `FontManager`.
+impl Default for Font {
+    fn default() -> Self {
+        font_manager().default_font().clone()
+    }
+}
+
+// Fonts are compared by the bits of their size, so they can be used as keys.
+impl PartialEq for Font {
+    fn eq(&self, other: &Self) -> bool {
+        self.typeface == other.typeface
+            && self.weight == other.weight
+            && self.width == other.width
+            && self.slant == other.slant
+            && self.size.to_bits() == other.size.to_bits()
+    }
+}
+
+impl Eq for Font {}
+
+impl Hash for Font {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.typeface.hash(state);
+        self.weight.hash(state);
+        self.width.hash(state);
+        self.slant.hash(state);
+        self.size.to_bits().hash(state);
     }
 }
@@ -0,0 +1,166 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::Path,
+    sync::{Mutex, MutexGuard, OnceLock},
+};
+
+use skia_safe::{
+    font_style::{Slant, Weight, Width},
+    textlayout::{FontCollection, TypefaceFontProvider},
+    FontMgr, Shaper, Typeface,
+};
+
+use super::font::{Font, FontSlant};
+
+static FONT_MANAGER: OnceLock<Mutex<FontManager>> = OnceLock::new();
+
+/// The font manager that is shared by all windows.
+pub fn font_manager() -> MutexGuard<'static, FontManager> {
+    FONT_MANAGER
+        .get_or_init(|| Mutex::new(FontManager::new()))
+        .lock()
+        .expect("Font manager lock poisoned")
+}
+
+#[derive(Debug)]
+pub enum FontError {
+    Io(std::io::Error),
+    /// The data isn't a font format that can be read.
+    InvalidData,
+    /// Neither the family nor any of the fallbacks are available.
+    NotFound(String),
+}
+
+impl Display for FontError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontError::Io(error) => write!(f, "Reading font failed: {}", error),
+            FontError::InvalidData => write!(f, "Font data is invalid"),
+            FontError::NotFound(family) => write!(f, "No font found for family '{}'", family),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+impl From<std::io::Error> for FontError {
+    fn from(error: std::io::Error) -> Self {
+        FontError::Io(error)
+    }
+}
+
+/// Resolves fonts to typefaces. Fonts that are registered by the application are matched first,
+/// then the fonts installed on the system. When a family can't be found the fallback families
+/// are tried in order, and finally the default font of the system.
+pub struct FontManager {
+    system: FontMgr,
+    registered: TypefaceFontProvider,
+    font_collection: FontCollection,
+    shaper: Shaper,
+    fallbacks: Vec<String>,
+    default_font: Font,
+}
+
+impl FontManager {
+    fn new() -> Self {
+        let system = FontMgr::new();
+        let registered = TypefaceFontProvider::new();
+        let mut font_collection = FontCollection::new();
+        font_collection.set_asset_font_manager(Some(registered.clone().into()));
+        font_collection.set_default_font_manager(system.clone(), None);
+
+        // The shaper finds fonts for characters that are missing from the requested font
+        let shaper = Shaper::new(system.clone());
+
+        Self {
+            system,
+            registered,
+            font_collection,
+            shaper,
+            fallbacks: Vec::new(),
+            default_font: Font::new("sans-serif", 24.0),
+        }
+    }
+
+    /// Registers the font in the file under the family name.
+    pub fn register_file(&mut self, family: &str, path: impl AsRef<Path>) -> Result<(), FontError> {
+        let bytes = fs::read(path)?;
+        self.register_bytes(family, &bytes)
+    }
+
+    /// Registers a font from memory, for example one embedded with `include_bytes!`.
+    /// Register every weight and slant of a family under the same name.
+    pub fn register_bytes(&mut self, family: &str, bytes: &[u8]) -> Result<(), FontError> {
+        let typeface = self
+            .system
+            .new_from_data(bytes, None)
+            .ok_or(FontError::InvalidData)?;
+        self.registered.register_typeface(typeface, Some(family));
+        self.font_collection.clear_caches();
+        Ok(())
+    }
+
+    /// Families that are tried in order when a font, or a character in it, isn't available.
+    pub fn set_fallbacks(&mut self, families: &[&str]) {
+        self.fallbacks = families.iter().map(|family| family.to_string()).collect();
+        self.font_collection.clear_caches();
+    }
+
+    pub fn set_default_font(&mut self, font: Font) {
+        self.default_font = font;
+    }
+
+    pub fn default_font(&self) -> &Font {
+        &self.default_font
+    }
+
+    /// The family of the font followed by the fallbacks.
+    pub fn families(&self, font: &Font) -> Vec<String> {
+        std::iter::once(font.typeface().to_string())
+            .chain(self.fallbacks.iter().cloned())
+            .collect()
+    }
+
+    pub fn typeface(&self, font: &Font) -> Result<Typeface, FontError> {
+        let style = skia_font_style(font);
+        self.families(font)
+            .iter()
+            .find_map(|family| {
+                self.registered
+                    .match_family_style(family, style)
+                    .or_else(|| self.system.match_family_style(family, style))
+            })
+            // An empty family name matches the default family of the system
+            .or_else(|| self.system.match_family_style("", style))
+            .ok_or_else(|| FontError::NotFound(font.typeface().to_string()))
+    }
+
+    /// The collection paragraphs find their fonts in. It shares its state with the manager, so
+    /// paragraphs are only built and laid out while the lock of `font_manager` is held.
+    pub(crate) fn font_collection(&self) -> FontCollection {
+        self.font_collection.clone()
+    }
+
+    pub(crate) fn shaper(&self) -> &Shaper {
+        &self.shaper
+    }
+}
+
+// Skia's font managers, font collection and shaper aren't thread safe. The manager is only used
+// behind the lock of `font_manager`, text is shaped with the lock held, and `Paragraph` holds it
+// while it builds a paragraph on the collection and while it lays one out, which is when the
+// paragraph looks up fonts. Laid out paragraphs are measured and painted without the collection.
+unsafe impl Send for FontManager {}
+
+pub(crate) fn skia_font_style(font: &Font) -> skia_safe::FontStyle {
+    skia_safe::FontStyle::new(
+        Weight::from(font.weight().0 as i32),
+        Width::from(font.width().0 as i32),
+        match font.slant() {
+            FontSlant::Upright => Slant::Upright,
+            FontSlant::Italic => Slant::Italic,
+            FontSlant::Oblique => Slant::Oblique,
+        },
+    )
+}
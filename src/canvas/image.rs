@@ -0,0 +1,144 @@
+use std::{fmt, io, path::Path};
+
+use crate::geo::Size;
+
+/// Why an image couldn't be loaded.
+#[derive(Debug)]
+pub enum ImageError {
+    Io(io::Error),
+    /// The data isn't an image in a supported format, or it is damaged.
+    Decode,
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Io(error) => write!(f, "Image could not be read: {}", error),
+            ImageError::Decode => write!(f, "Image could not be decoded"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<io::Error> for ImageError {
+    fn from(value: io::Error) -> Self {
+        ImageError::Io(value)
+    }
+}
+
+/// How pixels are picked when an image is drawn at another size than its own.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Sampling {
+    /// The closest pixel, keeps pixel art sharp.
+    Nearest,
+    /// Blends the four closest pixels.
+    #[default]
+    Linear,
+    /// Linear, and also between smaller copies of the image, for images drawn much smaller than they are.
+    Mipmap,
+    /// A smooth cubic filter, the best looking and the slowest.
+    Cubic,
+}
+
+/// Decoded pixels that can be drawn or used as a shader. Cloning an image doesn't copy its pixels.
+/// Images are equal when they are clones of each other, their pixels are not compared.
+#[derive(Clone)]
+pub struct Image {
+    image: skia_safe::Image,
+}
+
+impl Image {
+    /// Decodes an encoded PNG, JPEG, WebP or GIF image, of an animated GIF only the first frame.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        // Decode now rather than on the first draw, so that damaged data is reported here
+        skia_safe::Image::from_encoded(skia_safe::Data::new_copy(bytes))
+            .and_then(|image| image.to_raster_image(skia_safe::image::CachingHint::Allow))
+            .map(|image| Self { image })
+            .ok_or(ImageError::Decode)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Creates an image from rows of unpremultiplied RGBA pixels, without any padding between rows.
+    pub fn from_rgba(width: u32, height: u32, pixels: &[u8]) -> Option<Self> {
+        if pixels.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+
+        let info = skia_safe::ImageInfo::new(
+            (width as i32, height as i32),
+            skia_safe::ColorType::RGBA8888,
+            skia_safe::AlphaType::Unpremul,
+            None,
+        );
+        skia_safe::Image::from_raster_data(
+            &info,
+            skia_safe::Data::new_copy(pixels),
+            width as usize * 4,
+        )
+        .map(|image| Self { image })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.image.width() as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.image.height() as u32
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.image.width() as f32, self.image.height() as f32)
+    }
+
+    /// A copy of the image scaled to the size, none if the size is empty.
+    pub fn resized(&self, width: u32, height: u32, sampling: Sampling) -> Option<Self> {
+        let mut surface = skia_safe::Surface::new_raster_n32_premul((width as i32, height as i32))?;
+        surface.canvas().draw_image_rect_with_sampling_options(
+            &self.image,
+            None,
+            skia_safe::Rect::from_iwh(width as i32, height as i32),
+            skia_safe::SamplingOptions::from(sampling),
+            &skia_safe::Paint::default(),
+        );
+        Some(Self {
+            image: surface.image_snapshot(),
+        })
+    }
+
+    pub(crate) fn skia_image(&self) -> &skia_safe::Image {
+        &self.image
+    }
+}
+
+impl PartialEq for Image {
+    fn eq(&self, other: &Self) -> bool {
+        self.image.unique_id() == other.image.unique_id()
+    }
+}
+
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .finish()
+    }
+}
+
+// Only the size, a display list that is written out describes images rather than storing them.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Image {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut image = serializer.serialize_struct("Image", 2)?;
+        image.serialize_field("width", &self.width())?;
+        image.serialize_field("height", &self.height())?;
+        image.end()
+    }
+}
@@ -0,0 +1,329 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    thread,
+};
+
+use super::image::{Image, ImageError, Sampling};
+
+static IMAGE_CACHE: OnceLock<Mutex<ImageCache>> = OnceLock::new();
+
+/// The image cache that is shared by all windows.
+pub fn image_cache() -> MutexGuard<'static, ImageCache> {
+    IMAGE_CACHE
+        .get_or_init(|| Mutex::new(ImageCache::new()))
+        .lock()
+        .expect("Image cache lock poisoned")
+}
+
+/// Loads the image on a background thread and keeps it in the cache. The cache isn't locked while
+/// the image is read, decoded and scaled. Loads of the same image at the same size share a thread.
+pub fn load_in_background(source: &ImageSource, size: Option<(u32, u32)>) -> ImageLoad {
+    let key = (source.key(), size);
+    let load = {
+        let mut cache = image_cache();
+        if let Some(image) = cache.get(source, size) {
+            return ImageLoad::finished(Ok(image));
+        }
+        if let Some(load) = cache.pending.get(&key) {
+            return load.clone();
+        }
+        let load = ImageLoad::default();
+        cache.pending.insert(key.clone(), load.clone());
+        load
+    };
+
+    let source = source.clone();
+    let thread_load = load.clone();
+    thread::spawn(move || {
+        let result = decode_scaled(&source, size);
+        {
+            let mut cache = image_cache();
+            cache.pending.remove(&key);
+            if let Ok(image) = &result {
+                cache.insert(key, image.clone());
+            }
+        }
+        thread_load.finish(result);
+    });
+
+    load
+}
+
+// Decodes the image, or takes the decoded image from the cache, and scales it without holding the lock.
+fn decode_scaled(source: &ImageSource, size: Option<(u32, u32)>) -> Result<Image, ImageError> {
+    let cached = image_cache().get(source, None);
+    let image = match cached {
+        Some(image) => image,
+        None => {
+            let image = source.decode()?;
+            image_cache().insert((source.key(), None), image.clone());
+            image
+        }
+    };
+
+    match size {
+        Some((width, height)) if (image.width(), image.height()) != (width, height) => image
+            .resized(width, height, Sampling::Cubic)
+            .ok_or(ImageError::Decode),
+        _ => Ok(image),
+    }
+}
+
+/// An image that is loaded by `load_in_background`.
+pub type ImageLoad = Load<Image>;
+
+/// Something that is loaded on a background thread, clones wait for the same load.
+pub struct Load<T> {
+    result: Arc<Mutex<Option<Result<T, Arc<ImageError>>>>>,
+}
+
+impl<T: Clone> Load<T> {
+    pub(crate) fn finished(result: Result<T, ImageError>) -> Self {
+        let load = Self::default();
+        load.finish(result);
+        load
+    }
+
+    pub(crate) fn finish(&self, result: Result<T, ImageError>) {
+        *self.result.lock().expect("Load lock poisoned") = Some(result.map_err(Arc::new));
+    }
+
+    /// The result or the error once loading finished, `None` while it is still loading.
+    pub fn result(&self) -> Option<Result<T, Arc<ImageError>>> {
+        self.result.lock().expect("Load lock poisoned").clone()
+    }
+}
+
+impl<T> Clone for Load<T> {
+    fn clone(&self) -> Self {
+        Self {
+            result: self.result.clone(),
+        }
+    }
+}
+
+impl<T> Default for Load<T> {
+    fn default() -> Self {
+        Self {
+            result: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+/// Where an image is loaded from.
+#[derive(Clone)]
+pub enum ImageSource {
+    File(PathBuf),
+    /// Encoded image data, for example from `include_bytes!` or a download.
+    /// The key identifies the data in the cache, so different data needs different keys.
+    Bytes(String, Arc<[u8]>),
+}
+
+impl ImageSource {
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        ImageSource::File(path.into())
+    }
+
+    pub fn bytes(key: &str, bytes: impl Into<Arc<[u8]>>) -> Self {
+        ImageSource::Bytes(key.to_string(), bytes.into())
+    }
+
+    pub(crate) fn key(&self) -> SourceKey {
+        match self {
+            ImageSource::File(path) => SourceKey::File(path.clone()),
+            ImageSource::Bytes(key, _) => SourceKey::Bytes(key.clone()),
+        }
+    }
+
+    fn decode(&self) -> Result<Image, ImageError> {
+        match self {
+            ImageSource::File(path) => Image::from_file(path),
+            ImageSource::Bytes(_, bytes) => Image::from_bytes(bytes),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum SourceKey {
+    File(PathBuf),
+    Bytes(String),
+}
+
+// The source and the size in pixels the image was scaled to, none for the decoded size.
+type CacheKey = (SourceKey, Option<(u32, u32)>);
+
+/// Keeps decoded images, and copies scaled to the sizes they are drawn at, so that they are
+/// decoded and scaled once. When it is full the images that were used least recently are dropped.
+/// Images that failed to load are not kept, they are tried again the next time.
+pub struct ImageCache {
+    images: HashMap<CacheKey, Image>,
+    // Least recently used first
+    order: VecDeque<CacheKey>,
+    capacity: usize,
+    // Images that are loading on a background thread
+    pending: HashMap<CacheKey, ImageLoad>,
+}
+
+impl ImageCache {
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    pub fn new() -> Self {
+        Self {
+            images: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: Self::DEFAULT_CAPACITY,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// The number of images that are kept, scaled copies count as images of their own.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    /// The image if it is already in the cache, without loading it.
+    pub fn get(&mut self, source: &ImageSource, size: Option<(u32, u32)>) -> Option<Image> {
+        let key = (source.key(), size);
+        let image = self.images.get(&key).cloned();
+        if image.is_some() {
+            self.touch(&key);
+        }
+        image
+    }
+
+    /// The image from the cache, or loaded from the source and kept. With a size the image is
+    /// scaled to it in pixels, which is cheaper to draw than scaling a large image on every frame.
+    /// The cache stays locked while the image is decoded, widgets use `load_in_background`.
+    pub fn load(
+        &mut self,
+        source: &ImageSource,
+        size: Option<(u32, u32)>,
+    ) -> Result<Image, ImageError> {
+        if let Some(image) = self.get(source, size) {
+            return Ok(image);
+        }
+
+        let image = match size {
+            Some((width, height)) => {
+                let image = self.load(source, None)?;
+                if (image.width(), image.height()) == (width, height) {
+                    image
+                } else {
+                    image
+                        .resized(width, height, Sampling::Cubic)
+                        .ok_or(ImageError::Decode)?
+                }
+            }
+            None => source.decode()?,
+        };
+
+        self.insert((source.key(), size), image.clone());
+        Ok(image)
+    }
+
+    /// Drops the image and its scaled copies, for example after the file changed.
+    pub fn remove(&mut self, source: &ImageSource) {
+        let key = source.key();
+        self.images.retain(|(source_key, _), _| *source_key != key);
+        self.order.retain(|(source_key, _)| *source_key != key);
+    }
+
+    pub fn clear(&mut self) {
+        self.images.clear();
+        self.order.clear();
+    }
+
+    fn insert(&mut self, key: CacheKey, image: Image) {
+        if self.images.insert(key.clone(), image).is_some() {
+            self.touch(&key);
+        } else {
+            self.order.push_back(key);
+        }
+        self.evict();
+    }
+
+    fn touch(&mut self, key: &CacheKey) {
+        if let Some(index) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(index) {
+                self.order.push_back(key);
+            }
+        }
+    }
+
+    fn evict(&mut self) {
+        while self.order.len() > self.capacity {
+            if let Some(key) = self.order.pop_front() {
+                self.images.remove(&key);
+            }
+        }
+    }
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{image_cache, load_in_background, ImageLoad, ImageSource};
+
+    // A PNG with a single green pixel.
+    const PIXEL: [u8; 70] = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f,
+        0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8,
+        0xcf, 0xc0, 0xf0, 0x1f, 0x00, 0x05, 0x00, 0x01, 0xff, 0x89, 0x99, 0x3d, 0x1d, 0x00, 0x00,
+        0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    fn wait(load: &ImageLoad) {
+        let start = Instant::now();
+        while load.result().is_none() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "Image load timed out"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn loads_of_the_same_image_share_a_thread_until_it_is_cached() {
+        let source = ImageSource::bytes("image_cache::tests::pixel", PIXEL);
+        let first = load_in_background(&source, None);
+        let second = load_in_background(&source, None);
+        // Unless the first load already put the image in the cache, the second one waits for it
+        assert!(Arc::ptr_eq(&first.result, &second.result) || second.result().is_some());
+
+        wait(&first);
+        wait(&second);
+        let image = first.result().unwrap().unwrap();
+        assert_eq!((image.width(), image.height()), (1, 1));
+        assert!(image_cache().get(&source, None).is_some());
+        assert!(!image_cache().pending.contains_key(&(source.key(), None)));
+
+        let cached = load_in_background(&source, None);
+        assert!(!Arc::ptr_eq(&first.result, &cached.result));
+        assert!(cached.result().unwrap().is_ok());
+    }
+
+    #[test]
+    fn failed_loads_are_not_cached() {
+        let source = ImageSource::bytes("image_cache::tests::invalid", [0u8; 4]);
+        let load = load_in_background(&source, None);
+        wait(&load);
+        assert!(load.result().unwrap().is_err());
+        assert!(image_cache().get(&source, None).is_none());
+    }
+}
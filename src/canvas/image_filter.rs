@@ -0,0 +1,49 @@
+use super::shader::TileMode;
+
+/// An effect that is applied to the pixels of a layer, see `Layer::with_image_filter`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ImageFilter {
+    /// A gaussian blur with a standard deviation for each axis.
+    Blur {
+        sigma_x: f32,
+        sigma_y: f32,
+        tile_mode: TileMode,
+    },
+    /// Multiplies every color, as the column `[r, g, b, a, 1]`, with the 4 by 5 matrix in row major order.
+    /// Colors are from 0 to 1.
+    ColorMatrix([f32; 20]),
+    /// Applies the second filter to the result of the first.
+    Compose(Box<ImageFilter>, Box<ImageFilter>),
+}
+
+impl ImageFilter {
+    /// Blurs by the same amount in both directions. The edges fade out, like the edge of a layer does.
+    pub fn blur(sigma: f32) -> Self {
+        Self::Blur {
+            sigma_x: sigma,
+            sigma_y: sigma,
+            tile_mode: TileMode::Decal,
+        }
+    }
+
+    pub fn color_matrix(matrix: [f32; 20]) -> Self {
+        Self::ColorMatrix(matrix)
+    }
+
+    /// Removes the color, keeping the luminance.
+    pub fn grayscale() -> Self {
+        let (r, g, b) = (0.2126, 0.7152, 0.0722);
+        Self::ColorMatrix([
+            r, g, b, 0.0, 0.0, //
+            r, g, b, 0.0, 0.0, //
+            r, g, b, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ])
+    }
+
+    /// Applies this filter and then the next one.
+    pub fn then(self, next: ImageFilter) -> Self {
+        Self::Compose(Box::new(self), Box::new(next))
+    }
+}
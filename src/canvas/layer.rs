@@ -0,0 +1,83 @@
+use crate::geo::Rect;
+
+use super::{image_filter::ImageFilter, paint::BlendMode};
+
+/// An offscreen layer that `Canvas::save_layer` draws into, it is composited onto the canvas
+/// with its alpha and blend mode by the matching `restore`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Layer {
+    bounds: Option<Rect>,
+    alpha: f32,
+    blend_mode: BlendMode,
+    image_filter: Option<ImageFilter>,
+    backdrop: Option<ImageFilter>,
+}
+
+impl Layer {
+    pub fn new() -> Self {
+        Self {
+            bounds: None,
+            alpha: 1.0,
+            blend_mode: BlendMode::SrcOver,
+            image_filter: None,
+            backdrop: None,
+        }
+    }
+
+    /// Limits the layer to the rect, in local coordinates. Without bounds the layer covers the clip.
+    pub fn with_bounds(mut self, bounds: Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// The opacity the layer is composited with, from 0 to 1.
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    /// Applies the filter to the content of the layer when it is composited.
+    pub fn with_image_filter(mut self, image_filter: ImageFilter) -> Self {
+        self.image_filter = Some(image_filter);
+        self
+    }
+
+    /// Starts the layer with what is behind it passed through the filter,
+    /// a blur gives the frosted glass look of popups and sheets.
+    pub fn with_backdrop(mut self, backdrop: ImageFilter) -> Self {
+        self.backdrop = Some(backdrop);
+        self
+    }
+
+    pub fn bounds(&self) -> Option<&Rect> {
+        self.bounds.as_ref()
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    pub fn image_filter(&self) -> Option<&ImageFilter> {
+        self.image_filter.as_ref()
+    }
+
+    pub fn backdrop(&self) -> Option<&ImageFilter> {
+        self.backdrop.as_ref()
+    }
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
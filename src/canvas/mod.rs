@@ -1,28 +1,135 @@
-use self::{color::Color, font::Font, paint::Paint, text::Text};
-use crate::geo::{Point, Rect, Size};
+use self::{
+    color::Color,
+    font::Font,
+    image::{Image, Sampling},
+    layer::Layer,
+    nine_patch::Lattice,
+    paint::Paint,
+    paragraph::Paragraph,
+    path::Path,
+    shadow::Shadow,
+    svg::Svg,
+    text::{Text, TextAlignment},
+};
+use crate::geo::{Point, Rect, Size, Transform};
 
 pub mod canvas_renderer;
 pub mod color;
+pub mod display_list;
 pub mod font;
+pub mod font_manager;
+pub mod image;
+pub mod image_cache;
+pub mod image_filter;
+pub mod layer;
+pub mod nine_patch;
 pub mod paint;
+pub mod paragraph;
+pub mod path;
+pub mod recording_canvas;
+pub mod rich_text;
+pub mod shader;
+pub mod shadow;
 pub mod skia_cpu_canvas;
+pub mod svg;
 pub mod text;
+pub mod text_measurer;
 pub trait Canvas: Send {
     fn clear(&mut self, color: &Color);
 
     fn save(&mut self);
+    /// Like `save`, but everything until the matching `restore` is drawn into a layer that is then
+    /// composited onto the canvas as a whole.
+    fn save_layer(&mut self, layer: &Layer);
     fn restore(&mut self);
     fn translate(&mut self, point: &Point);
     fn scale(&mut self, size: &Size);
+    /// Rotates clockwise by the angle in degrees around (0, 0).
+    fn rotate(&mut self, degrees: f32);
+    /// Slants by the tangents of the skew angles, see `Transform::skew`.
+    fn skew(&mut self, x: f32, y: f32);
+    /// Applies the transform before the current one, to everything that is drawn after it.
+    fn concat(&mut self, transform: &Transform);
 
     fn draw_rect(&mut self, rect: &Rect, paint: &Paint);
     fn draw_rounded_rect(&mut self, rect: &Rect, rx: f32, ry: f32, paint: &Paint);
 
     fn draw_circle(&mut self, center: &Point, radius: f32, paint: &Paint);
+    fn draw_oval(&mut self, rect: &Rect, paint: &Paint);
+    /// Draws the part of the oval in the rect between the angles, in degrees clockwise from the positive
+    /// x axis. With `use_center` the arc is closed through the center, like a pie slice.
+    fn draw_arc(
+        &mut self,
+        oval: &Rect,
+        start_angle: f32,
+        sweep_angle: f32,
+        use_center: bool,
+        paint: &Paint,
+    );
+    fn draw_path(&mut self, path: &Path, paint: &Paint);
+    /// Draws the shadow of the filled path, use `Path::add_rect` or `Path::add_rounded_rect` for boxes.
+    /// The shape itself is not drawn, it is painted on top of its shadow.
+    fn draw_shadow(&mut self, path: &Path, shadow: &Shadow);
+    fn draw_line(&mut self, from: &Point, to: &Point, paint: &Paint);
+    /// Draws lines through the points, without closing them.
+    fn draw_polyline(&mut self, points: &[Point], paint: &Paint);
 
-    fn draw_string(&mut self, rect: &Rect, text: &str, font: &Font, paint: &Paint);
-    fn draw_text(&mut self, text: &Text, rect: &Rect, paint: &Paint);
+    /// Draws the image at its own size with its top left corner at the position.
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        position: &Point,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    );
+    /// Draws the part of the image in `src`, or all of it, scaled into `dst`. Filtering never
+    /// samples pixels outside of `src`, so parts of an image can be drawn next to each other
+    /// without the neighboring pixels bleeding in.
+    fn draw_image_rect(
+        &mut self,
+        image: &Image,
+        src: Option<&Rect>,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    );
+
+    /// Draws the image into `dst` split in nine parts by `center`, in pixels of the image.
+    /// The corners keep their size, the edges stretch along their side and the center in both
+    /// directions. When `dst` is smaller than the corners they shrink.
+    fn draw_image_nine(
+        &mut self,
+        image: &Image,
+        center: &Rect,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    );
+    /// Like `draw_image_nine`, with any number of fixed and stretching columns and rows.
+    fn draw_image_lattice(
+        &mut self,
+        image: &Image,
+        lattice: &Lattice,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    );
+    /// Draws the SVG document scaled into the rect. With a paint the document is drawn in the color
+    /// of the paint, keeping only its shape and alpha, which tints single color icons.
+    fn draw_svg(&mut self, svg: &Svg, dst: &Rect, paint: Option<&Paint>);
+
+    fn draw_string(
+        &mut self,
+        rect: &Rect,
+        text: &str,
+        font: &Font,
+        alignment: TextAlignment,
+        paint: &Paint,
+    );
+    fn draw_text(&mut self, text: &Text, rect: &Rect, alignment: TextAlignment, paint: &Paint);
     fn pixels(&mut self) -> Option<&[u8]>;
     fn clip_rect(&mut self, rect: &Rect);
-    // fn draw_paragraph(&mut self, pos: &Point, paragraph: &Paragraph);
+    fn clip_rounded_rect(&mut self, rect: &Rect, rx: f32, ry: f32);
+    fn clip_path(&mut self, path: &Path);
+    fn draw_paragraph(&mut self, pos: &Point, paragraph: &Paragraph);
 }
@@ -0,0 +1,235 @@
+use crate::geo::{Point, Rect, Size};
+
+use super::{
+    image::{Image, Sampling},
+    paint::Paint,
+    Canvas,
+};
+
+/// Divides an image into a grid for `Canvas::draw_image_lattice`. The divisions are in pixels of
+/// the image and increasing. Columns and rows alternate between keeping their size and stretching,
+/// starting with one that keeps its size, so a first division of 0 starts with a stretching one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Lattice {
+    x_divs: Vec<u32>,
+    y_divs: Vec<u32>,
+}
+
+impl Lattice {
+    pub fn new(x_divs: Vec<u32>, y_divs: Vec<u32>) -> Self {
+        Self { x_divs, y_divs }
+    }
+
+    pub fn x_divs(&self) -> &[u32] {
+        &self.x_divs
+    }
+
+    pub fn y_divs(&self) -> &[u32] {
+        &self.y_divs
+    }
+}
+
+/// How the edges and the center of a nine-patch fill the space between the corners.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum NinePatchFill {
+    #[default]
+    Stretch,
+    /// Repeats them at their own size, for skins with a pattern that must not be distorted.
+    /// The last repetition is cut off.
+    Tile,
+}
+
+/// A skin for boxes made from an image split into nine parts by its center rect, like a CSS
+/// border image. The corners keep their size, the edges follow the sides of the box and the
+/// center fills the rest.
+#[derive(Clone)]
+pub struct NinePatch {
+    image: Image,
+    center: Rect,
+    fill: NinePatchFill,
+    scale: f32,
+    sampling: Sampling,
+}
+
+impl NinePatch {
+    /// The center is in pixels of the image.
+    pub fn new(image: Image, center: Rect) -> Self {
+        Self {
+            image,
+            center,
+            fill: NinePatchFill::default(),
+            scale: 1.0,
+            sampling: Sampling::default(),
+        }
+    }
+
+    /// Splits the image by the widths of the borders, in pixels of the image.
+    pub fn from_borders(image: Image, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        let size = image.size();
+        let center = Rect::new(
+            Point::new(left, top),
+            Size::new(
+                (size.width - left - right).max(0.0),
+                (size.height - top - bottom).max(0.0),
+            ),
+        );
+        Self::new(image, center)
+    }
+
+    pub fn with_fill(mut self, fill: NinePatchFill) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// The number of image pixels per point, 2 for skins made for screens with a scale factor of 2.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn center(&self) -> &Rect {
+        &self.center
+    }
+
+    /// Draws the skin into the rect.
+    pub fn draw(&self, canvas: &mut dyn Canvas, dst: &Rect, paint: Option<&Paint>) {
+        if self.scale <= 0.0 {
+            return;
+        }
+
+        // Draw in image pixels so that the corners keep their size in pixels
+        canvas.save();
+        canvas.scale(&Size::new(1.0 / self.scale, 1.0 / self.scale));
+        let dst = Rect::new(
+            Point::new(dst.left() * self.scale, dst.top() * self.scale),
+            Size::new(
+                dst.size().width * self.scale,
+                dst.size().height * self.scale,
+            ),
+        );
+        match self.fill {
+            NinePatchFill::Stretch => {
+                canvas.draw_image_nine(&self.image, &self.center, &dst, self.sampling, paint)
+            }
+            NinePatchFill::Tile => self.draw_tiled(canvas, &dst, paint),
+        }
+        canvas.restore();
+    }
+
+    fn draw_tiled(&self, canvas: &mut dyn Canvas, dst: &Rect, paint: Option<&Paint>) {
+        let size = self.image.size();
+        let columns = divide(0.0, self.center.left(), self.center.right(), size.width);
+        let rows = divide(0.0, self.center.top(), self.center.bottom(), size.height);
+        let dst_columns = divide_dst(dst.left(), dst.right(), &columns);
+        let dst_rows = divide_dst(dst.top(), dst.bottom(), &rows);
+
+        for (row, (src_row, dst_row)) in rows.iter().zip(&dst_rows).enumerate() {
+            for (column, (src_column, dst_column)) in columns.iter().zip(&dst_columns).enumerate() {
+                let src = Rect::new(
+                    Point::new(src_column.0, src_row.0),
+                    Size::new(src_column.1 - src_column.0, src_row.1 - src_row.0),
+                );
+                let region = Rect::new(
+                    Point::new(dst_column.0, dst_row.0),
+                    Size::new(dst_column.1 - dst_column.0, dst_row.1 - dst_row.0),
+                );
+                if src.size().width <= 0.0
+                    || src.size().height <= 0.0
+                    || region.size().width <= 0.0
+                    || region.size().height <= 0.0
+                {
+                    continue;
+                }
+
+                // The middle column and row repeat, the corners and the other sides fit the region
+                let tile = Size::new(
+                    if column == 1 {
+                        src.size().width
+                    } else {
+                        region.size().width
+                    },
+                    if row == 1 {
+                        src.size().height
+                    } else {
+                        region.size().height
+                    },
+                );
+                canvas.save();
+                canvas.clip_rect(&region);
+                let mut y = region.top();
+                while y < region.bottom() {
+                    let mut x = region.left();
+                    while x < region.right() {
+                        canvas.draw_image_rect(
+                            &self.image,
+                            Some(&src),
+                            &Rect::new(Point::new(x, y), tile),
+                            self.sampling,
+                            paint,
+                        );
+                        x += tile.width;
+                    }
+                    y += tile.height;
+                }
+                canvas.restore();
+            }
+        }
+    }
+}
+
+// The start and the end of the three parts along one side of the image.
+fn divide(start: f32, center_start: f32, center_end: f32, end: f32) -> [(f32, f32); 3] {
+    let center_start = center_start.clamp(start, end);
+    let center_end = center_end.clamp(center_start, end);
+    [
+        (start, center_start),
+        (center_start, center_end),
+        (center_end, end),
+    ]
+}
+
+// The three parts along one side of the destination, the borders shrink evenly when they don't fit.
+fn divide_dst(start: f32, end: f32, src: &[(f32, f32); 3]) -> [(f32, f32); 3] {
+    let first = src[0].1 - src[0].0;
+    let last = src[2].1 - src[2].0;
+    let length = end - start;
+    let scale = if first + last > length && first + last > 0.0 {
+        length / (first + last)
+    } else {
+        1.0
+    };
+    divide(start, start + first * scale, end - last * scale, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::divide_dst;
+
+    #[test]
+    fn borders_keep_their_size_and_the_center_stretches() {
+        let src = [(0.0, 4.0), (4.0, 6.0), (6.0, 10.0)];
+        assert_eq!(
+            divide_dst(10.0, 40.0, &src),
+            [(10.0, 14.0), (14.0, 36.0), (36.0, 40.0)]
+        );
+    }
+
+    #[test]
+    fn borders_shrink_evenly_when_they_dont_fit() {
+        let src = [(0.0, 4.0), (4.0, 6.0), (6.0, 10.0)];
+        assert_eq!(
+            divide_dst(0.0, 4.0, &src),
+            [(0.0, 2.0), (2.0, 2.0), (2.0, 4.0)]
+        );
+    }
+}
@@ -1,18 +1,260 @@
-use super::color::Color;
+use super::{color::Color, shader::Shader};
 
-#[derive(Default)]
+/// Whether shapes are filled, outlined or both.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PaintStyle {
+    #[default]
+    Fill,
+    Stroke,
+    FillAndStroke,
+}
+
+/// How the colors that are drawn are combined with the colors that are already on the canvas.
+/// The names and formulas are those of the Porter-Duff operators and the separable and
+/// non-separable blend modes of CSS.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum BlendMode {
+    Clear,
+    Src,
+    Dst,
+    /// Draws on top, the default.
+    #[default]
+    SrcOver,
+    DstOver,
+    SrcIn,
+    DstIn,
+    SrcOut,
+    DstOut,
+    SrcATop,
+    DstATop,
+    Xor,
+    Plus,
+    Modulate,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Multiply,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+/// The shape at the ends of open strokes.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StrokeCap {
+    /// Ends exactly at the end point.
+    #[default]
+    Butt,
+    /// A half circle around the end point.
+    Round,
+    /// A half square around the end point.
+    Square,
+}
+
+/// The shape at the corners of strokes.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StrokeJoin {
+    /// A sharp corner, it is beveled instead when it would reach further than the miter limit.
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// Alternating lengths of dashes and gaps along a stroke.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Dash {
+    intervals: Vec<f32>,
+    phase: f32,
+}
+
+impl Dash {
+    /// The intervals start with a dash and alternate with gaps. Like skia, intervals that can't
+    /// dash a stroke give no dash: an odd number of them, negative ones, or a sum of zero.
+    pub fn new(intervals: Vec<f32>) -> Option<Self> {
+        let valid = intervals.len() >= 2
+            && intervals.len() % 2 == 0
+            && intervals.iter().all(|interval| *interval >= 0.0)
+            && intervals.iter().sum::<f32>() > 0.0;
+        valid.then_some(Self {
+            intervals,
+            phase: 0.0,
+        })
+    }
+
+    /// How far into the intervals the stroke starts, moving it makes the dashes crawl.
+    pub fn with_phase(mut self, phase: f32) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    pub fn intervals(&self) -> &[f32] {
+        &self.intervals
+    }
+
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+}
+
+/// How shapes are colored and outlined.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Paint {
     color: Color,
+    style: PaintStyle,
+    stroke_width: f32,
+    stroke_cap: StrokeCap,
+    stroke_join: StrokeJoin,
+    miter_limit: f32,
+    dash: Option<Dash>,
+    anti_alias: bool,
+    shader: Option<Shader>,
+    blend_mode: BlendMode,
 }
 
 impl Paint {
     pub fn new(color: impl Into<Color>) -> Self {
         Self {
             color: color.into(),
+            ..Default::default()
         }
     }
 
+    /// An opaque paint that only draws the shader.
+    pub fn new_shader(shader: impl Into<Shader>) -> Self {
+        Self::new(Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        })
+        .with_shader(shader)
+    }
+
+    /// A paint that outlines shapes with lines of the width.
+    pub fn new_stroke(color: impl Into<Color>, width: f32) -> Self {
+        Self::new(color)
+            .with_style(PaintStyle::Stroke)
+            .with_stroke_width(width)
+    }
+
+    pub fn with_style(mut self, style: PaintStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The width of strokes, zero draws lines that are one pixel wide at any scale.
+    pub fn with_stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    pub fn with_stroke_cap(mut self, stroke_cap: StrokeCap) -> Self {
+        self.stroke_cap = stroke_cap;
+        self
+    }
+
+    pub fn with_stroke_join(mut self, stroke_join: StrokeJoin) -> Self {
+        self.stroke_join = stroke_join;
+        self
+    }
+
+    /// How long a miter join may become, as a multiple of the stroke width, before it is beveled.
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    pub fn with_dash(mut self, dash: Dash) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
+    /// Colors shapes with a gradient or image, the color of the paint is only used for its alpha.
+    pub fn with_shader(mut self, shader: impl Into<Shader>) -> Self {
+        self.shader = Some(shader.into());
+        self
+    }
+
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    /// Smooths the edges of shapes, on by default. Turning it off keeps thin lines on whole pixels sharp.
+    pub fn with_anti_alias(mut self, anti_alias: bool) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+
     pub fn color(&self) -> &Color {
         &self.color
     }
+
+    pub fn style(&self) -> PaintStyle {
+        self.style
+    }
+
+    pub fn stroke_width(&self) -> f32 {
+        self.stroke_width
+    }
+
+    pub fn stroke_cap(&self) -> StrokeCap {
+        self.stroke_cap
+    }
+
+    pub fn stroke_join(&self) -> StrokeJoin {
+        self.stroke_join
+    }
+
+    pub fn miter_limit(&self) -> f32 {
+        self.miter_limit
+    }
+
+    pub fn dash(&self) -> Option<&Dash> {
+        self.dash.as_ref()
+    }
+
+    pub fn anti_alias(&self) -> bool {
+        self.anti_alias
+    }
+
+    pub fn shader(&self) -> Option<&Shader> {
+        self.shader.as_ref()
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Self {
+            color: Color::default(),
+            style: PaintStyle::Fill,
+            stroke_width: 0.0,
+            stroke_cap: StrokeCap::Butt,
+            stroke_join: StrokeJoin::Miter,
+            miter_limit: 4.0,
+            dash: None,
+            anti_alias: true,
+            shader: None,
+            blend_mode: BlendMode::SrcOver,
+        }
+    }
 }
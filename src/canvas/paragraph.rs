@@ -0,0 +1,567 @@
+use std::{
+    fmt,
+    ops::Range,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use skia_safe::textlayout::{
+    self, ParagraphBuilder, RectHeightStyle, RectWidthStyle, TextDecoration, TextStyle,
+};
+
+use crate::{
+    geo::{Point, Rect, Size},
+    widget::style::{HorizontalAlignment, TextDirection},
+};
+
+use super::{
+    color::{Color, Color32f},
+    font::Font,
+    font_manager::{font_manager, skia_font_style, FontManager},
+    text_measurer::TextMetrics,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+    Justify,
+    /// Left for left to right text, right for right to left text.
+    Start,
+    End,
+}
+
+impl From<HorizontalAlignment> for TextAlign {
+    fn from(alignment: HorizontalAlignment) -> Self {
+        match alignment {
+            HorizontalAlignment::Left => TextAlign::Left,
+            HorizontalAlignment::Center => TextAlign::Center,
+            HorizontalAlignment::Right => TextAlign::Right,
+            HorizontalAlignment::Start => TextAlign::Start,
+            HorizontalAlignment::End => TextAlign::End,
+        }
+    }
+}
+
+/// How the lines of a paragraph are broken, aligned and colored.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParagraphStyle {
+    align: TextAlign,
+    max_lines: Option<usize>,
+    ellipsis: Option<String>,
+    line_height: Option<f32>,
+    color: Color,
+    text_direction: TextDirection,
+}
+
+impl ParagraphStyle {
+    pub fn new() -> Self {
+        Self {
+            align: TextAlign::Left,
+            max_lines: None,
+            ellipsis: None,
+            line_height: None,
+            color: Color32f::new_grey(0.0).into(),
+            text_direction: TextDirection::LeftToRight,
+        }
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Text that replaces the end of the last line when the text doesn't fit in `max_lines`.
+    pub fn with_ellipsis(mut self, ellipsis: &str) -> Self {
+        self.ellipsis = Some(ellipsis.to_string());
+        self
+    }
+
+    /// Line height as a multiple of the font size.
+    pub fn with_line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// The base direction of the text, it decides the order of runs in mixed text and where lines start.
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction
+    }
+
+    pub fn align(&self) -> TextAlign {
+        self.align
+    }
+
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    pub fn line_height(&self) -> Option<f32> {
+        self.line_height
+    }
+}
+
+impl Default for ParagraphStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct LineMetrics {
+    /// Distance from the top of the paragraph to the baseline of the line.
+    pub baseline: f32,
+    pub ascent: f32,
+    pub descent: f32,
+    pub left: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+struct Layout {
+    paragraph: textlayout::Paragraph,
+    width: Option<f32>,
+    text_direction: TextDirection,
+}
+
+/// Text that is broken into lines to fit a width.
+/// The paragraph is shaped once, changing the width only breaks the lines again.
+/// It is shared between layout and the render thread, so the laid out lines are behind a lock.
+/// Cloning a paragraph doesn't shape it again, the clones share their lines.
+#[derive(Clone)]
+pub struct Paragraph {
+    text: String,
+    // The text and style of every run, to build copies with lines of their own
+    runs: Arc<[(String, TextStyle)]>,
+    font: Font,
+    style: ParagraphStyle,
+    cap_height: f32,
+    font_ascent: f32,
+    layout: Arc<Mutex<Layout>>,
+}
+
+impl Paragraph {
+    pub fn new(text: &str, font: Font, style: ParagraphStyle) -> Self {
+        let text_style = Self::text_style(&font, &style);
+        Self::new_with_runs(&[(text, text_style)], font, style)
+    }
+
+    /// Builds a paragraph from runs of text that each have their own style.
+    pub(crate) fn new_with_runs(
+        runs: &[(&str, TextStyle)],
+        font: Font,
+        style: ParagraphStyle,
+    ) -> Self {
+        let runs: Arc<[(String, TextStyle)]> = runs
+            .iter()
+            .map(|(text, text_style)| (text.to_string(), text_style.clone()))
+            .collect();
+        let paragraph = Self::build(&runs, &font, &style);
+        let (_, metrics) = skia_safe::Font::from(&font).metrics();
+
+        Self {
+            text: runs.iter().map(|(text, _)| text.as_str()).collect(),
+            runs,
+            font,
+            cap_height: metrics.cap_height,
+            font_ascent: -metrics.ascent,
+            layout: Arc::new(Mutex::new(Layout {
+                paragraph,
+                width: None,
+                text_direction: style.text_direction,
+            })),
+            style,
+        }
+    }
+
+    /// A copy of the paragraph with lines of its own, laid out for the width.
+    /// Laying out a clone would break the lines of this paragraph too.
+    pub fn copy_with_width(&self, width: f32) -> Self {
+        let text_direction = self.text_direction();
+        let style = self.style.clone().with_text_direction(text_direction);
+        let copy = Self {
+            text: self.text.clone(),
+            runs: self.runs.clone(),
+            font: self.font.clone(),
+            style: self.style.clone(),
+            cap_height: self.cap_height,
+            font_ascent: self.font_ascent,
+            layout: Arc::new(Mutex::new(Layout {
+                paragraph: Self::build(&self.runs, &self.font, &style),
+                width: None,
+                text_direction,
+            })),
+        };
+        copy.layout(width);
+        copy
+    }
+
+    fn build(
+        runs: &[(String, TextStyle)],
+        font: &Font,
+        style: &ParagraphStyle,
+    ) -> textlayout::Paragraph {
+        // The font collection isn't thread safe, the paragraph is built with the lock held
+        let font_manager = font_manager();
+        let mut paragraph_style = textlayout::ParagraphStyle::new();
+        paragraph_style
+            .set_text_style(&Self::text_style_with(&font_manager, font, style))
+            .set_text_align(match style.align {
+                TextAlign::Left => textlayout::TextAlign::Left,
+                TextAlign::Center => textlayout::TextAlign::Center,
+                TextAlign::Right => textlayout::TextAlign::Right,
+                TextAlign::Justify => textlayout::TextAlign::Justify,
+                TextAlign::Start => textlayout::TextAlign::Start,
+                TextAlign::End => textlayout::TextAlign::End,
+            })
+            .set_text_direction(match style.text_direction {
+                TextDirection::LeftToRight => textlayout::TextDirection::LTR,
+                TextDirection::RightToLeft => textlayout::TextDirection::RTL,
+            })
+            .set_max_lines(style.max_lines);
+        if let Some(ellipsis) = &style.ellipsis {
+            paragraph_style.set_ellipsis(ellipsis);
+        }
+
+        let mut builder = ParagraphBuilder::new(&paragraph_style, font_manager.font_collection());
+        for (text, text_style) in runs {
+            builder.push_style(text_style).add_text(text).pop();
+        }
+        builder.build()
+    }
+
+    /// The text style for text in the given font and paragraph style, runs start from this.
+    pub(crate) fn text_style(font: &Font, style: &ParagraphStyle) -> TextStyle {
+        Self::text_style_with(&font_manager(), font, style)
+    }
+
+    fn text_style_with(
+        font_manager: &FontManager,
+        font: &Font,
+        style: &ParagraphStyle,
+    ) -> TextStyle {
+        let mut text_style = TextStyle::new();
+        // The fallbacks let the paragraph find glyphs that are missing from the font
+        let families = font_manager.families(font);
+        text_style
+            .set_font_families(&families)
+            .set_font_size(font.size())
+            .set_font_style(skia_font_style(font))
+            .set_color(skia_safe::Color4f::from(&style.color).to_color());
+        if let Some(line_height) = style.line_height {
+            text_style.set_height_override(true).set_height(line_height);
+        }
+
+        text_style
+    }
+
+    fn lock(&self) -> MutexGuard<Layout> {
+        self.layout.lock().expect("Paragraph layout lock poisoned")
+    }
+
+    /// Breaks the text into lines that fit the width, does nothing if it already is.
+    /// Pass `f32::INFINITY` to only break at explicit line breaks.
+    pub fn layout(&self, width: f32) {
+        let mut layout = self.lock();
+        if layout.width != Some(width) {
+            // Shaping looks up fonts in the font collection, which isn't thread safe
+            let _font_manager = font_manager();
+            layout.paragraph.layout(width);
+            layout.width = Some(width);
+        }
+    }
+
+    /// Shapes the text again for the direction if it was shaped for the other one.
+    /// Widgets call this with the direction they inherit before laying out and painting.
+    pub fn set_text_direction(&self, text_direction: TextDirection) {
+        let mut layout = self.lock();
+        if layout.text_direction != text_direction {
+            let style = self.style.clone().with_text_direction(text_direction);
+            layout.paragraph = Self::build(&self.runs, &self.font, &style);
+            layout.width = None;
+            layout.text_direction = text_direction;
+        }
+    }
+
+    /// The direction the text is shaped for, the style's until `set_text_direction` changes it.
+    pub fn text_direction(&self) -> TextDirection {
+        self.lock().text_direction
+    }
+
+    /// The size of the laid out text, as wide as its longest line.
+    pub fn size(&self) -> Size {
+        let layout = self.lock();
+        let paragraph = &layout.paragraph;
+        let width = if self.style.align == TextAlign::Justify {
+            paragraph.max_width()
+        } else {
+            paragraph.longest_line()
+        };
+
+        Size::new(width.ceil(), paragraph.height().ceil())
+    }
+
+    /// The laid out paragraph as a block of text, its ascent reaches from the top to the first baseline.
+    pub fn text_metrics(&self) -> TextMetrics {
+        let size = self.size();
+        let baseline = self.alphabetic_baseline();
+        TextMetrics {
+            width: size.width,
+            ascent: baseline,
+            descent: size.height - baseline,
+            cap_height: self.cap_height,
+            font_ascent: self.font_ascent,
+        }
+    }
+
+    /// The width of the text when it isn't wrapped at all.
+    pub fn max_intrinsic_width(&self) -> f32 {
+        self.lock().paragraph.max_intrinsic_width()
+    }
+
+    /// The width of the longest word, the paragraph can't become narrower than this without overflowing.
+    pub fn min_intrinsic_width(&self) -> f32 {
+        self.lock().paragraph.min_intrinsic_width()
+    }
+
+    /// Distance from the top of the paragraph to the baseline of the first line.
+    pub fn alphabetic_baseline(&self) -> f32 {
+        self.lock().paragraph.alphabetic_baseline()
+    }
+
+    pub fn ideographic_baseline(&self) -> f32 {
+        self.lock().paragraph.ideographic_baseline()
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lock().paragraph.line_number()
+    }
+
+    /// Whether lines were dropped, or ellipsized, because of `max_lines`.
+    pub fn did_exceed_max_lines(&self) -> bool {
+        self.lock().paragraph.did_exceed_max_lines()
+    }
+
+    pub fn line_metrics(&self) -> Vec<LineMetrics> {
+        self.lock()
+            .paragraph
+            .get_line_metrics()
+            .iter()
+            .map(|line| LineMetrics {
+                baseline: line.baseline as f32,
+                ascent: line.ascent as f32,
+                descent: line.descent as f32,
+                left: line.left as f32,
+                width: line.width as f32,
+                height: line.height as f32,
+            })
+            .collect()
+    }
+
+    /// The boxes that the text in the range, in UTF-16 code units, covers after layout.
+    /// A range that spans multiple lines returns a box per line.
+    pub fn rects_for_range(&self, range: Range<usize>) -> Vec<Rect> {
+        self.lock()
+            .paragraph
+            .get_rects_for_range(range, RectHeightStyle::Max, RectWidthStyle::Tight)
+            .iter()
+            .map(|text_box| {
+                Rect::new(
+                    Point::new(text_box.rect.left(), text_box.rect.top()),
+                    Size::new(text_box.rect.width(), text_box.rect.height()),
+                )
+            })
+            .collect()
+    }
+
+    /// The position in the text, in UTF-16 code units, of the caret that is closest to the point.
+    pub fn index_at(&self, position: &Point) -> usize {
+        let position = self
+            .lock()
+            .paragraph
+            .get_glyph_position_at_coordinate((position.x, position.y))
+            .position;
+        position.max(0) as usize
+    }
+
+    /// The range of the word around the index, both in UTF-16 code units.
+    pub fn word_at(&self, index: usize) -> Range<usize> {
+        self.lock().paragraph.get_word_boundary(index as u32)
+    }
+
+    /// The range of the line, between explicit line breaks, around the index, both in UTF-16 code units.
+    /// The line break itself is not part of the range.
+    pub fn line_at(&self, index: usize) -> Range<usize> {
+        let text: Vec<u16> = self.text.encode_utf16().collect();
+        let index = index.min(text.len());
+        let newline = '\n' as u16;
+        let start = text[..index]
+            .iter()
+            .rposition(|unit| *unit == newline)
+            .map_or(0, |position| position + 1);
+        let end = text[index..]
+            .iter()
+            .position(|unit| *unit == newline)
+            .map_or(text.len(), |position| index + position);
+
+        start..end
+    }
+
+    /// The text in the range of UTF-16 code units.
+    pub fn text_in_range(&self, range: Range<usize>) -> String {
+        let text: Vec<u16> = self.text.encode_utf16().collect();
+        let end = range.end.min(text.len());
+        let start = range.start.min(end);
+        String::from_utf16_lossy(&text[start..end])
+    }
+
+    /// The length of the text in UTF-16 code units, the unit of the ranges and indices of the paragraph.
+    pub fn utf16_len(&self) -> usize {
+        self.text.encode_utf16().count()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    pub fn style(&self) -> &ParagraphStyle {
+        &self.style
+    }
+
+    /// The runs of text with what their styles set, in the order they were added.
+    pub(crate) fn runs(&self) -> impl Iterator<Item = Run<'_>> {
+        self.runs.iter().map(|(text, style)| Run::new(text, style))
+    }
+
+    /// The width the lines were last broken for, none before the first `layout`.
+    pub fn layout_width(&self) -> Option<f32> {
+        self.lock().width
+    }
+
+    pub(crate) fn paint(&self, canvas: &mut skia_safe::Canvas, position: skia_safe::Point) {
+        self.lock().paragraph.paint(canvas, position);
+    }
+}
+
+// Paragraphs are equal when they show the same text the same way, their shaping is not compared.
+impl PartialEq for Paragraph {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+            && self.font == other.font
+            && self.style == other.style
+            && self.runs == other.runs
+    }
+}
+
+impl fmt::Debug for Paragraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paragraph")
+            .field("text", &self.text)
+            .field("font", &self.font)
+            .field("style", &self.style)
+            .field("runs", &self.runs().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Paragraph {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut paragraph = serializer.serialize_struct("Paragraph", 4)?;
+        paragraph.serialize_field("text", &self.text)?;
+        paragraph.serialize_field("font", &self.font)?;
+        paragraph.serialize_field("style", &self.style)?;
+        paragraph.serialize_field("runs", &self.runs().collect::<Vec<_>>())?;
+        paragraph.end()
+    }
+}
+
+/// A run of a paragraph with the parts of its style that fonts and spans set.
+/// Colors are red, green, blue and alpha.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub(crate) struct Run<'a> {
+    text: &'a str,
+    families: Vec<String>,
+    size: f32,
+    weight: i32,
+    italic: bool,
+    color: [u8; 4],
+    background: Option<[u8; 4]>,
+    underline: bool,
+    strikethrough: bool,
+}
+
+impl<'a> Run<'a> {
+    fn new(text: &'a str, style: &TextStyle) -> Self {
+        let rgba = |color: skia_safe::Color| [color.r(), color.g(), color.b(), color.a()];
+        let background = style.background().color();
+        let font_style = style.font_style();
+        let decoration = style.decoration_type();
+
+        Self {
+            text,
+            families: style.font_families().iter().map(String::from).collect(),
+            size: style.font_size(),
+            weight: *font_style.weight(),
+            italic: font_style.slant() != skia_safe::font_style::Slant::Upright,
+            color: rgba(style.color()),
+            background: (background != TextStyle::new().background().color())
+                .then(|| rgba(background)),
+            underline: decoration.contains(TextDecoration::UNDERLINE),
+            strikethrough: decoration.contains(TextDecoration::LINE_THROUGH),
+        }
+    }
+}
+
+// How the dump of a display list prints the run.
+impl fmt::Display for Run<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = |[r, g, b, a]: [u8; 4]| format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a);
+        write!(
+            f,
+            "[{:?} {} {} {}",
+            self.text,
+            self.families.join(","),
+            self.size,
+            self.weight
+        )?;
+        if self.italic {
+            write!(f, " italic")?;
+        }
+        write!(f, " {}", hex(self.color))?;
+        if let Some(background) = self.background {
+            write!(f, " background {}", hex(background))?;
+        }
+        if self.underline {
+            write!(f, " underline")?;
+        }
+        if self.strikethrough {
+            write!(f, " strikethrough")?;
+        }
+        write!(f, "]")
+    }
+}
@@ -0,0 +1,132 @@
+use crate::geo::{Point, Rect};
+
+/// Decides which parts of a path that crosses itself are inside of it.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FillRule {
+    /// Inside if the outlines around a point wind around it a different number of times in each direction.
+    #[default]
+    NonZero,
+    /// Inside if a ray from a point crosses the outline an odd number of times.
+    EvenOdd,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PathCommand {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo(Point, Point),
+    CubicTo(Point, Point, Point),
+    /// A circular arc with the radius that is tangent to the line towards the first point and the line
+    /// from there to the second point.
+    ArcTo(Point, Point, f32),
+    /// A part of the oval, angles are in degrees clockwise from the positive x axis.
+    Arc(Rect, f32, f32),
+    Rect(Rect),
+    /// A rect with corners rounded by the radii in x and y.
+    RoundedRect(Rect, f32, f32),
+    Oval(Rect),
+    Close,
+}
+
+/// An outline made of lines and curves, it can be filled or stroked by `Canvas::draw_path`.
+/// The path only records the commands, the canvas turns them into the shapes of its backend.
+#[derive(Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Path {
+    commands: Vec<PathCommand>,
+    fill_rule: FillRule,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new contour at the point.
+    pub fn move_to(&mut self, point: Point) -> &mut Self {
+        self.commands.push(PathCommand::MoveTo(point));
+        self
+    }
+
+    pub fn line_to(&mut self, point: Point) -> &mut Self {
+        self.commands.push(PathCommand::LineTo(point));
+        self
+    }
+
+    /// A quadratic bezier curve that is pulled towards the control point.
+    pub fn quad_to(&mut self, control: Point, point: Point) -> &mut Self {
+        self.commands.push(PathCommand::QuadTo(control, point));
+        self
+    }
+
+    /// A cubic bezier curve that leaves in the direction of the first and arrives from the direction of
+    /// the second control point.
+    pub fn cubic_to(&mut self, control1: Point, control2: Point, point: Point) -> &mut Self {
+        self.commands
+            .push(PathCommand::CubicTo(control1, control2, point));
+        self
+    }
+
+    /// Rounds the corner at `corner` on the way to `point` with a circular arc of the radius,
+    /// like the `arcTo` of html canvas. The path ends where the arc ends, not at `point`.
+    pub fn arc_to(&mut self, corner: Point, point: Point, radius: f32) -> &mut Self {
+        self.commands
+            .push(PathCommand::ArcTo(corner, point, radius));
+        self
+    }
+
+    /// Continues with a part of the oval in the rect, connected to the contour by a line.
+    pub fn add_arc(&mut self, oval: Rect, start_angle: f32, sweep_angle: f32) -> &mut Self {
+        self.commands
+            .push(PathCommand::Arc(oval, start_angle, sweep_angle));
+        self
+    }
+
+    /// Adds the rect as a closed contour of its own.
+    pub fn add_rect(&mut self, rect: Rect) -> &mut Self {
+        self.commands.push(PathCommand::Rect(rect));
+        self
+    }
+
+    /// Adds the rect with rounded corners as a closed contour of its own.
+    pub fn add_rounded_rect(&mut self, rect: Rect, rx: f32, ry: f32) -> &mut Self {
+        self.commands.push(PathCommand::RoundedRect(rect, rx, ry));
+        self
+    }
+
+    /// Adds the oval inside of the rect as a closed contour of its own.
+    pub fn add_oval(&mut self, oval: Rect) -> &mut Self {
+        self.commands.push(PathCommand::Oval(oval));
+        self
+    }
+
+    /// Closes the contour with a line back to its start.
+    pub fn close(&mut self) -> &mut Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) -> &mut Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Whether the point is inside of the filled path, following its fill rule.
+    pub fn contains(&self, point: &Point) -> bool {
+        skia_safe::Path::from(self).contains(*point)
+    }
+}
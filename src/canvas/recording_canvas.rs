@@ -0,0 +1,295 @@
+use crate::geo::{Point, Rect, Size, Transform};
+
+use super::{
+    color::Color,
+    display_list::{DisplayList, DrawCommand},
+    font::Font,
+    image::{Image, Sampling},
+    layer::Layer,
+    nine_patch::Lattice,
+    paint::Paint,
+    paragraph::Paragraph,
+    path::Path,
+    shadow::Shadow,
+    svg::Svg,
+    text::{Text, TextAlignment},
+    Canvas,
+};
+
+/// A canvas that doesn't draw, it records the calls into a `DisplayList`.
+/// Painters can be tested by comparing what they recorded instead of pixels,
+/// and a recording can be drawn later by replaying it onto a `SkiaCanvas`.
+#[derive(Default)]
+pub struct RecordingCanvas {
+    display_list: DisplayList,
+}
+
+impl RecordingCanvas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn display_list(&self) -> &DisplayList {
+        &self.display_list
+    }
+
+    /// The recording so far, the canvas starts a new one.
+    pub fn take_display_list(&mut self) -> DisplayList {
+        std::mem::take(&mut self.display_list)
+    }
+
+    pub fn into_display_list(self) -> DisplayList {
+        self.display_list
+    }
+
+    fn record(&mut self, command: DrawCommand) {
+        self.display_list.push(command);
+    }
+}
+
+impl Canvas for RecordingCanvas {
+    fn clear(&mut self, color: &Color) {
+        self.record(DrawCommand::Clear(*color));
+    }
+
+    fn save(&mut self) {
+        self.record(DrawCommand::Save);
+    }
+
+    fn save_layer(&mut self, layer: &Layer) {
+        self.record(DrawCommand::SaveLayer(layer.clone()));
+    }
+
+    fn restore(&mut self) {
+        self.record(DrawCommand::Restore);
+    }
+
+    fn translate(&mut self, point: &Point) {
+        self.record(DrawCommand::Translate(*point));
+    }
+
+    fn scale(&mut self, size: &Size) {
+        self.record(DrawCommand::Scale(*size));
+    }
+
+    fn rotate(&mut self, degrees: f32) {
+        self.record(DrawCommand::Rotate(degrees));
+    }
+
+    fn skew(&mut self, x: f32, y: f32) {
+        self.record(DrawCommand::Skew(x, y));
+    }
+
+    fn concat(&mut self, transform: &Transform) {
+        self.record(DrawCommand::Concat(*transform));
+    }
+
+    fn draw_rect(&mut self, rect: &Rect, paint: &Paint) {
+        self.record(DrawCommand::DrawRect {
+            rect: *rect,
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_rounded_rect(&mut self, rect: &Rect, rx: f32, ry: f32, paint: &Paint) {
+        self.record(DrawCommand::DrawRoundedRect {
+            rect: *rect,
+            rx,
+            ry,
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_circle(&mut self, center: &Point, radius: f32, paint: &Paint) {
+        self.record(DrawCommand::DrawCircle {
+            center: *center,
+            radius,
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_oval(&mut self, rect: &Rect, paint: &Paint) {
+        self.record(DrawCommand::DrawOval {
+            rect: *rect,
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_arc(
+        &mut self,
+        oval: &Rect,
+        start_angle: f32,
+        sweep_angle: f32,
+        use_center: bool,
+        paint: &Paint,
+    ) {
+        self.record(DrawCommand::DrawArc {
+            oval: *oval,
+            start_angle,
+            sweep_angle,
+            use_center,
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_path(&mut self, path: &Path, paint: &Paint) {
+        self.record(DrawCommand::DrawPath {
+            path: path.clone(),
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_shadow(&mut self, path: &Path, shadow: &Shadow) {
+        self.record(DrawCommand::DrawShadow {
+            path: path.clone(),
+            shadow: *shadow,
+        });
+    }
+
+    fn draw_line(&mut self, from: &Point, to: &Point, paint: &Paint) {
+        self.record(DrawCommand::DrawLine {
+            from: *from,
+            to: *to,
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_polyline(&mut self, points: &[Point], paint: &Paint) {
+        self.record(DrawCommand::DrawPolyline {
+            points: points.to_vec(),
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        position: &Point,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        self.record(DrawCommand::DrawImage {
+            image: image.clone(),
+            position: *position,
+            sampling,
+            paint: paint.cloned(),
+        });
+    }
+
+    fn draw_image_rect(
+        &mut self,
+        image: &Image,
+        src: Option<&Rect>,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        self.record(DrawCommand::DrawImageRect {
+            image: image.clone(),
+            src: src.copied(),
+            dst: *dst,
+            sampling,
+            paint: paint.cloned(),
+        });
+    }
+
+    fn draw_image_nine(
+        &mut self,
+        image: &Image,
+        center: &Rect,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        self.record(DrawCommand::DrawImageNine {
+            image: image.clone(),
+            center: *center,
+            dst: *dst,
+            sampling,
+            paint: paint.cloned(),
+        });
+    }
+
+    fn draw_image_lattice(
+        &mut self,
+        image: &Image,
+        lattice: &Lattice,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        self.record(DrawCommand::DrawImageLattice {
+            image: image.clone(),
+            lattice: lattice.clone(),
+            dst: *dst,
+            sampling,
+            paint: paint.cloned(),
+        });
+    }
+
+    fn draw_svg(&mut self, svg: &Svg, dst: &Rect, paint: Option<&Paint>) {
+        self.record(DrawCommand::DrawSvg {
+            svg: svg.clone(),
+            dst: *dst,
+            paint: paint.cloned(),
+        });
+    }
+
+    fn draw_string(
+        &mut self,
+        rect: &Rect,
+        text: &str,
+        font: &Font,
+        alignment: TextAlignment,
+        paint: &Paint,
+    ) {
+        self.record(DrawCommand::DrawText {
+            text: text.to_string(),
+            font: font.clone(),
+            text_direction: alignment.text_direction,
+            rect: *rect,
+            alignment,
+            paint: paint.clone(),
+        });
+    }
+
+    fn draw_text(&mut self, text: &Text, rect: &Rect, alignment: TextAlignment, paint: &Paint) {
+        self.record(DrawCommand::DrawText {
+            text: text.text().to_string(),
+            font: text.font().clone(),
+            text_direction: text.text_direction(),
+            rect: *rect,
+            alignment,
+            paint: paint.clone(),
+        });
+    }
+
+    // There are no pixels, nothing is drawn.
+    fn pixels(&mut self) -> Option<&[u8]> {
+        None
+    }
+
+    fn clip_rect(&mut self, rect: &Rect) {
+        self.record(DrawCommand::ClipRect(*rect));
+    }
+
+    fn clip_rounded_rect(&mut self, rect: &Rect, rx: f32, ry: f32) {
+        self.record(DrawCommand::ClipRoundedRect {
+            rect: *rect,
+            rx,
+            ry,
+        });
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        self.record(DrawCommand::ClipPath(path.clone()));
+    }
+
+    fn draw_paragraph(&mut self, pos: &Point, paragraph: &Paragraph) {
+        self.record(DrawCommand::DrawParagraph {
+            position: *pos,
+            paragraph: paragraph.clone(),
+            width: paragraph.layout_width(),
+        });
+    }
+}
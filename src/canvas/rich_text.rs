@@ -0,0 +1,237 @@
+use std::ops::Range;
+
+use skia_safe::textlayout::{TextDecoration, TextStyle};
+
+use crate::{
+    geo::{Point, Size},
+    widget::style::TextDirection,
+};
+
+use super::{
+    color::Color,
+    font::{Font, FontWeight},
+    font_manager::{font_manager, skia_font_style},
+    paragraph::{Paragraph, ParagraphStyle},
+};
+
+/// The style of a span of rich text. Everything that isn't set is taken from the paragraph.
+#[derive(Clone, Default)]
+pub struct SpanStyle {
+    font: Option<Font>,
+    size: Option<f32>,
+    weight: Option<FontWeight>,
+    color: Option<Color>,
+    background: Option<Color>,
+    underline: bool,
+    strikethrough: bool,
+}
+
+impl SpanStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn with_background(mut self, color: impl Into<Color>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    pub fn with_underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn with_strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    fn apply(&self, text_style: &mut TextStyle) {
+        if let Some(font) = &self.font {
+            let families = font_manager().families(font);
+            text_style
+                .set_font_families(&families)
+                .set_font_size(font.size())
+                .set_font_style(skia_font_style(font));
+        }
+
+        if let Some(size) = self.size {
+            text_style.set_font_size(size);
+        }
+
+        if let Some(weight) = self.weight {
+            let font_style = text_style.font_style();
+            text_style.set_font_style(skia_safe::FontStyle::new(
+                (weight.0 as i32).into(),
+                font_style.width(),
+                font_style.slant(),
+            ));
+        }
+
+        if let Some(color) = &self.color {
+            text_style.set_color(skia_safe::Color4f::from(color).to_color());
+        }
+
+        if let Some(background) = &self.background {
+            let paint = skia_safe::Paint::new(skia_safe::Color4f::from(background), None);
+            text_style.set_background_color(&paint);
+        }
+
+        let mut decoration = TextDecoration::NO_DECORATION;
+        if self.underline {
+            decoration |= TextDecoration::UNDERLINE;
+        }
+        if self.strikethrough {
+            decoration |= TextDecoration::LINE_THROUGH;
+        }
+        text_style.set_decoration_type(decoration);
+    }
+}
+
+#[derive(Clone)]
+pub struct TextSpan {
+    text: String,
+    style: SpanStyle,
+}
+
+impl TextSpan {
+    pub fn new(text: impl Into<String>, style: SpanStyle) -> Self {
+        Self {
+            text: text.into(),
+            style,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// A paragraph made of spans that each have their own style.
+/// Spans can be hit tested, to find out which one is under the mouse.
+pub struct RichText {
+    paragraph: Paragraph,
+    // The range of every span in UTF-16 code units, which is what the paragraph counts in.
+    ranges: Vec<Range<usize>>,
+}
+
+impl RichText {
+    pub fn new(spans: &[TextSpan], font: Font, style: ParagraphStyle) -> Self {
+        let base = Paragraph::text_style(&font, &style);
+        let runs: Vec<(&str, TextStyle)> = spans
+            .iter()
+            .map(|span| {
+                let mut text_style = base.clone();
+                span.style.apply(&mut text_style);
+                (span.text.as_str(), text_style)
+            })
+            .collect();
+
+        let mut start = 0;
+        let ranges = spans
+            .iter()
+            .map(|span| {
+                let end = start + span.text.encode_utf16().count();
+                let range = start..end;
+                start = end;
+                range
+            })
+            .collect();
+
+        Self {
+            paragraph: Paragraph::new_with_runs(&runs, font, style),
+            ranges,
+        }
+    }
+
+    pub fn layout(&self, width: f32) {
+        self.paragraph.layout(width)
+    }
+
+    /// Shapes the text for the direction it inherits, see `Paragraph::set_text_direction`.
+    pub fn set_text_direction(&self, text_direction: TextDirection) {
+        self.paragraph.set_text_direction(text_direction)
+    }
+
+    pub fn size(&self) -> Size {
+        self.paragraph.size()
+    }
+
+    pub fn paragraph(&self) -> &Paragraph {
+        &self.paragraph
+    }
+
+    /// The index of the span at the position, relative to the top left of the text.
+    pub fn hit_test(&self, position: &Point) -> Option<usize> {
+        self.ranges.iter().position(|range| {
+            self.paragraph
+                .rects_for_range(range.clone())
+                .iter()
+                .any(|rect| rect.hit_test(position))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RichText, SpanStyle, TextSpan};
+    use crate::{
+        canvas::{font::Font, paragraph::ParagraphStyle},
+        geo::Point,
+    };
+
+    fn rich_text(spans: &[&str]) -> RichText {
+        let spans: Vec<TextSpan> = spans
+            .iter()
+            .map(|text| TextSpan::new(*text, SpanStyle::new()))
+            .collect();
+        RichText::new(&spans, Font::default(), ParagraphStyle::new())
+    }
+
+    #[test]
+    fn ranges_count_utf16_code_units() {
+        let rich_text = rich_text(&["a😀", "é", "", "x"]);
+        assert_eq!(rich_text.ranges, vec![0..3, 3..4, 4..4, 4..5]);
+        assert_eq!(rich_text.paragraph().utf16_len(), 5);
+    }
+
+    #[test]
+    fn hit_test_finds_the_span_under_the_position() {
+        let rich_text = rich_text(&["first ", "second"]);
+        rich_text.layout(f32::INFINITY);
+
+        for (span, range) in rich_text.ranges.iter().enumerate() {
+            let rect = rich_text.paragraph().rects_for_range(range.clone())[0];
+            let center = Point::new(
+                (rect.left() + rect.right()) / 2.0,
+                (rect.top() + rect.bottom()) / 2.0,
+            );
+            assert_eq!(rich_text.hit_test(&center), Some(span));
+        }
+        let size = rich_text.size();
+        assert_eq!(
+            rich_text.hit_test(&Point::new(size.width + 10.0, size.height / 2.0)),
+            None
+        );
+    }
+}
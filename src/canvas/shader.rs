@@ -0,0 +1,186 @@
+use crate::geo::Point;
+
+use super::{color::Color, image::Image};
+
+/// What a shader draws outside of the area it is defined for.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TileMode {
+    /// Repeats the edge color.
+    #[default]
+    Clamp,
+    Repeat,
+    /// Repeats, with every other repetition mirrored.
+    Mirror,
+    /// Draws nothing.
+    Decal,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum GradientKind {
+    Linear {
+        start: Point,
+        end: Point,
+    },
+    Radial {
+        center: Point,
+        radius: f32,
+    },
+    /// Goes around the center, angles are in degrees clockwise from the positive x axis.
+    Sweep {
+        center: Point,
+        start_angle: f32,
+        end_angle: f32,
+    },
+    /// Goes from the start circle to the end circle.
+    Conical {
+        start: Point,
+        start_radius: f32,
+        end: Point,
+        end_radius: f32,
+    },
+}
+
+/// Colors that blend into each other along a line, a radius or an angle.
+/// Coordinates are in the local coordinates of whatever is painted.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Gradient {
+    kind: GradientKind,
+    stops: Vec<(f32, Color)>,
+    tile_mode: TileMode,
+}
+
+impl Gradient {
+    fn new(kind: GradientKind) -> Self {
+        Self {
+            kind,
+            stops: Vec::new(),
+            tile_mode: TileMode::Clamp,
+        }
+    }
+
+    pub fn linear(start: Point, end: Point) -> Self {
+        Self::new(GradientKind::Linear { start, end })
+    }
+
+    pub fn radial(center: Point, radius: f32) -> Self {
+        Self::new(GradientKind::Radial { center, radius })
+    }
+
+    /// A gradient that goes once around the center.
+    pub fn sweep(center: Point) -> Self {
+        Self::new(GradientKind::Sweep {
+            center,
+            start_angle: 0.0,
+            end_angle: 360.0,
+        })
+    }
+
+    /// A gradient that goes around the center between two angles.
+    pub fn sweep_between(center: Point, start_angle: f32, end_angle: f32) -> Self {
+        Self::new(GradientKind::Sweep {
+            center,
+            start_angle,
+            end_angle,
+        })
+    }
+
+    pub fn conical(start: Point, start_radius: f32, end: Point, end_radius: f32) -> Self {
+        Self::new(GradientKind::Conical {
+            start,
+            start_radius,
+            end,
+            end_radius,
+        })
+    }
+
+    /// Adds a color at the offset, from 0 at the start to 1 at the end of the gradient.
+    /// Stops have to be added in order of their offsets.
+    pub fn with_stop(mut self, offset: f32, color: impl Into<Color>) -> Self {
+        self.stops.push((offset, color.into()));
+        self
+    }
+
+    /// Spreads the colors evenly over the gradient.
+    pub fn with_colors(mut self, colors: &[Color]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        self.stops = colors
+            .iter()
+            .enumerate()
+            .map(|(index, color)| (index as f32 / last, *color))
+            .collect();
+        self
+    }
+
+    pub fn with_tile_mode(mut self, tile_mode: TileMode) -> Self {
+        self.tile_mode = tile_mode;
+        self
+    }
+
+    pub fn kind(&self) -> &GradientKind {
+        &self.kind
+    }
+
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    pub fn tile_mode(&self) -> TileMode {
+        self.tile_mode
+    }
+}
+
+/// Fills with an image, placed with its top left corner at the origin and repeated by the tile modes.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ImageShader {
+    image: Image,
+    tile_mode_x: TileMode,
+    tile_mode_y: TileMode,
+}
+
+impl ImageShader {
+    pub fn new(image: Image) -> Self {
+        Self {
+            image,
+            tile_mode_x: TileMode::Clamp,
+            tile_mode_y: TileMode::Clamp,
+        }
+    }
+
+    pub fn with_tile_modes(mut self, tile_mode_x: TileMode, tile_mode_y: TileMode) -> Self {
+        self.tile_mode_x = tile_mode_x;
+        self.tile_mode_y = tile_mode_y;
+        self
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn tile_modes(&self) -> (TileMode, TileMode) {
+        (self.tile_mode_x, self.tile_mode_y)
+    }
+}
+
+/// Colors a shape per pixel instead of with the single color of the paint.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Shader {
+    Gradient(Gradient),
+    Image(ImageShader),
+}
+
+impl From<Gradient> for Shader {
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+impl From<ImageShader> for Shader {
+    fn from(image_shader: ImageShader) -> Self {
+        Self::Image(image_shader)
+    }
+}
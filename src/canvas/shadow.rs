@@ -0,0 +1,63 @@
+use crate::geo::Point;
+
+use super::color::Color;
+
+/// A blurred copy of a shape that is drawn behind it, like the `box-shadow` of CSS.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Shadow {
+    color: Color,
+    offset: Point,
+    blur_radius: f32,
+    spread: f32,
+}
+
+impl Shadow {
+    pub fn new(color: impl Into<Color>) -> Self {
+        Self {
+            color: color.into(),
+            offset: Point::new(0.0, 0.0),
+            blur_radius: 0.0,
+            spread: 0.0,
+        }
+    }
+
+    /// How far the shadow is moved away from the shape.
+    pub fn with_offset(mut self, offset: Point) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// How far the edge of the shadow is blurred, zero gives a sharp edge.
+    pub fn with_blur_radius(mut self, blur_radius: f32) -> Self {
+        self.blur_radius = blur_radius.max(0.0);
+        self
+    }
+
+    /// Grows the shadow on all sides before it is blurred.
+    pub fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread.max(0.0);
+        self
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn offset(&self) -> Point {
+        self.offset
+    }
+
+    pub fn blur_radius(&self) -> f32 {
+        self.blur_radius
+    }
+
+    pub fn spread(&self) -> f32 {
+        self.spread
+    }
+
+    /// The standard deviation of the gaussian blur, half of the blur radius as in CSS.
+    pub fn blur_sigma(&self) -> f32 {
+        self.blur_radius / 2.0
+    }
+}
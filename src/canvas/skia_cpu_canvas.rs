@@ -1,6 +1,22 @@
-use super::{color::Color, font::Font, paint::Paint, text::Text, Canvas};
-use crate::geo::{self, Rect, Size};
-use skia_safe::{utils::text_utils::Align, ISize, Point, Surface, TextBlob};
+use super::{
+    color::Color,
+    font::Font,
+    font_manager::font_manager,
+    image::{Image, Sampling},
+    image_filter::ImageFilter,
+    layer::Layer,
+    nine_patch::Lattice,
+    paint::{BlendMode, Paint, PaintStyle, StrokeCap, StrokeJoin},
+    paragraph::Paragraph,
+    path::{FillRule, Path, PathCommand},
+    shader::{Gradient, GradientKind, Shader, TileMode},
+    shadow::Shadow,
+    svg::Svg,
+    text::{Text, TextAlignment},
+    Canvas,
+};
+use crate::geo::{self, Rect, Size, Transform};
+use skia_safe::{ISize, Point, Surface};
 
 pub struct SkiaCanvas {
     surface: Surface,
@@ -89,17 +105,249 @@ impl From<&Paint> for skia_safe::Paint {
     fn from(value: &Paint) -> Self {
         let [r, g, b, a] = value.color().as_floats();
         let mut p = skia_safe::Paint::new(skia_safe::Color4f::new(r, g, b, a), None);
-        p.set_anti_alias(true);
+        p.set_anti_alias(value.anti_alias())
+            .set_style(match value.style() {
+                PaintStyle::Fill => skia_safe::paint::Style::Fill,
+                PaintStyle::Stroke => skia_safe::paint::Style::Stroke,
+                PaintStyle::FillAndStroke => skia_safe::paint::Style::StrokeAndFill,
+            })
+            .set_stroke_width(value.stroke_width())
+            .set_stroke_cap(match value.stroke_cap() {
+                StrokeCap::Butt => skia_safe::paint::Cap::Butt,
+                StrokeCap::Round => skia_safe::paint::Cap::Round,
+                StrokeCap::Square => skia_safe::paint::Cap::Square,
+            })
+            .set_stroke_join(match value.stroke_join() {
+                StrokeJoin::Miter => skia_safe::paint::Join::Miter,
+                StrokeJoin::Round => skia_safe::paint::Join::Round,
+                StrokeJoin::Bevel => skia_safe::paint::Join::Bevel,
+            })
+            .set_stroke_miter(value.miter_limit())
+            .set_blend_mode(value.blend_mode().into());
+        if let Some(dash) = value.dash() {
+            p.set_path_effect(skia_safe::PathEffect::dash(dash.intervals(), dash.phase()));
+        }
+        if let Some(shader) = value.shader() {
+            p.set_shader(skia_shader(shader));
+        }
         p
     }
 }
 
+impl From<&Transform> for skia_safe::Matrix {
+    fn from(value: &Transform) -> Self {
+        skia_safe::Matrix::new_all(
+            value.a, value.c, value.e, value.b, value.d, value.f, 0.0, 0.0, 1.0,
+        )
+    }
+}
+
+impl From<&Path> for skia_safe::Path {
+    fn from(value: &Path) -> Self {
+        let mut path = skia_safe::Path::new();
+        path.set_fill_type(match value.fill_rule() {
+            FillRule::NonZero => skia_safe::PathFillType::Winding,
+            FillRule::EvenOdd => skia_safe::PathFillType::EvenOdd,
+        });
+        for command in value.commands() {
+            match *command {
+                PathCommand::MoveTo(point) => path.move_to(point),
+                PathCommand::LineTo(point) => path.line_to(point),
+                PathCommand::QuadTo(control, point) => path.quad_to(control, point),
+                PathCommand::CubicTo(control1, control2, point) => {
+                    path.cubic_to(control1, control2, point)
+                }
+                PathCommand::ArcTo(corner, point, radius) => {
+                    path.arc_to_tangent(corner, point, radius)
+                }
+                PathCommand::Arc(oval, start_angle, sweep_angle) => {
+                    path.arc_to(skia_safe::Rect::from(oval), start_angle, sweep_angle, false)
+                }
+                PathCommand::Rect(rect) => path.add_rect(skia_safe::Rect::from(rect), None),
+                PathCommand::RoundedRect(rect, rx, ry) => path.add_rrect(
+                    skia_safe::RRect::new_rect_xy(skia_safe::Rect::from(rect), rx, ry),
+                    None,
+                ),
+                PathCommand::Oval(oval) => path.add_oval(skia_safe::Rect::from(oval), None),
+                PathCommand::Close => path.close(),
+            };
+        }
+
+        path
+    }
+}
+
+impl From<BlendMode> for skia_safe::BlendMode {
+    fn from(value: BlendMode) -> Self {
+        match value {
+            BlendMode::Clear => skia_safe::BlendMode::Clear,
+            BlendMode::Src => skia_safe::BlendMode::Src,
+            BlendMode::Dst => skia_safe::BlendMode::Dst,
+            BlendMode::SrcOver => skia_safe::BlendMode::SrcOver,
+            BlendMode::DstOver => skia_safe::BlendMode::DstOver,
+            BlendMode::SrcIn => skia_safe::BlendMode::SrcIn,
+            BlendMode::DstIn => skia_safe::BlendMode::DstIn,
+            BlendMode::SrcOut => skia_safe::BlendMode::SrcOut,
+            BlendMode::DstOut => skia_safe::BlendMode::DstOut,
+            BlendMode::SrcATop => skia_safe::BlendMode::SrcATop,
+            BlendMode::DstATop => skia_safe::BlendMode::DstATop,
+            BlendMode::Xor => skia_safe::BlendMode::Xor,
+            BlendMode::Plus => skia_safe::BlendMode::Plus,
+            BlendMode::Modulate => skia_safe::BlendMode::Modulate,
+            BlendMode::Screen => skia_safe::BlendMode::Screen,
+            BlendMode::Overlay => skia_safe::BlendMode::Overlay,
+            BlendMode::Darken => skia_safe::BlendMode::Darken,
+            BlendMode::Lighten => skia_safe::BlendMode::Lighten,
+            BlendMode::ColorDodge => skia_safe::BlendMode::ColorDodge,
+            BlendMode::ColorBurn => skia_safe::BlendMode::ColorBurn,
+            BlendMode::HardLight => skia_safe::BlendMode::HardLight,
+            BlendMode::SoftLight => skia_safe::BlendMode::SoftLight,
+            BlendMode::Difference => skia_safe::BlendMode::Difference,
+            BlendMode::Exclusion => skia_safe::BlendMode::Exclusion,
+            BlendMode::Multiply => skia_safe::BlendMode::Multiply,
+            BlendMode::Hue => skia_safe::BlendMode::Hue,
+            BlendMode::Saturation => skia_safe::BlendMode::Saturation,
+            BlendMode::Color => skia_safe::BlendMode::Color,
+            BlendMode::Luminosity => skia_safe::BlendMode::Luminosity,
+        }
+    }
+}
+
+impl From<TileMode> for skia_safe::TileMode {
+    fn from(value: TileMode) -> Self {
+        match value {
+            TileMode::Clamp => skia_safe::TileMode::Clamp,
+            TileMode::Repeat => skia_safe::TileMode::Repeat,
+            TileMode::Mirror => skia_safe::TileMode::Mirror,
+            TileMode::Decal => skia_safe::TileMode::Decal,
+        }
+    }
+}
+
+impl From<Sampling> for skia_safe::SamplingOptions {
+    fn from(value: Sampling) -> Self {
+        match value {
+            Sampling::Nearest => skia_safe::SamplingOptions::new(
+                skia_safe::FilterMode::Nearest,
+                skia_safe::MipmapMode::None,
+            ),
+            Sampling::Linear => skia_safe::SamplingOptions::new(
+                skia_safe::FilterMode::Linear,
+                skia_safe::MipmapMode::None,
+            ),
+            Sampling::Mipmap => skia_safe::SamplingOptions::new(
+                skia_safe::FilterMode::Linear,
+                skia_safe::MipmapMode::Linear,
+            ),
+            Sampling::Cubic => skia_safe::CubicResampler::mitchell().into(),
+        }
+    }
+}
+
+// Nine-patches and lattices don't take mipmaps or cubic sampling.
+fn filter_mode(sampling: Sampling) -> skia_safe::FilterMode {
+    match sampling {
+        Sampling::Nearest => skia_safe::FilterMode::Nearest,
+        _ => skia_safe::FilterMode::Linear,
+    }
+}
+
+// Skia refuses gradients with less than two stops, the paint then falls back to its color.
+fn skia_gradient(gradient: &Gradient) -> Option<skia_safe::Shader> {
+    let colors: Vec<skia_safe::Color4f> = gradient
+        .stops()
+        .iter()
+        .map(|(_, color)| color.into())
+        .collect();
+    let offsets: Vec<f32> = gradient.stops().iter().map(|(offset, _)| *offset).collect();
+    let colors = colors.as_slice();
+    let offsets = Some(offsets.as_slice());
+    let tile_mode = gradient.tile_mode().into();
+
+    match *gradient.kind() {
+        GradientKind::Linear { start, end } => {
+            skia_safe::Shader::linear_gradient((start, end), colors, offsets, tile_mode, None, None)
+        }
+        GradientKind::Radial { center, radius } => skia_safe::Shader::radial_gradient(
+            center, radius, colors, offsets, tile_mode, None, None,
+        ),
+        GradientKind::Sweep {
+            center,
+            start_angle,
+            end_angle,
+        } => skia_safe::Shader::sweep_gradient(
+            center,
+            colors,
+            offsets,
+            tile_mode,
+            (start_angle, end_angle),
+            None,
+            None,
+        ),
+        GradientKind::Conical {
+            start,
+            start_radius,
+            end,
+            end_radius,
+        } => skia_safe::Shader::two_point_conical_gradient(
+            start,
+            start_radius,
+            end,
+            end_radius,
+            colors,
+            offsets,
+            tile_mode,
+            None,
+            None,
+        ),
+    }
+}
+
+fn skia_shader(shader: &Shader) -> Option<skia_safe::Shader> {
+    match shader {
+        Shader::Gradient(gradient) => skia_gradient(gradient),
+        Shader::Image(image_shader) => {
+            let (tile_mode_x, tile_mode_y) = image_shader.tile_modes();
+            image_shader.image().skia_image().to_shader(
+                (tile_mode_x.into(), tile_mode_y.into()),
+                skia_safe::SamplingOptions::new(
+                    skia_safe::FilterMode::Linear,
+                    skia_safe::MipmapMode::None,
+                ),
+                None,
+            )
+        }
+    }
+}
+
+fn skia_image_filter(filter: &ImageFilter) -> Option<skia_safe::ImageFilter> {
+    match filter {
+        ImageFilter::Blur {
+            sigma_x,
+            sigma_y,
+            tile_mode,
+        } => skia_safe::image_filters::blur(
+            (*sigma_x, *sigma_y),
+            skia_safe::TileMode::from(*tile_mode),
+            None,
+            None,
+        ),
+        ImageFilter::ColorMatrix(matrix) => skia_safe::image_filters::color_filter(
+            skia_safe::color_filters::matrix_row_major(matrix),
+            None,
+            None,
+        ),
+        ImageFilter::Compose(first, second) => {
+            skia_safe::image_filters::compose(skia_image_filter(second)?, skia_image_filter(first)?)
+        }
+    }
+}
+
 impl From<&Font> for skia_safe::Font {
     fn from(value: &Font) -> Self {
-        let mut font = skia_safe::Font::new(
-            skia_safe::Typeface::new(value.typeface(), skia_safe::FontStyle::normal()).unwrap(),
-            value.size(),
-        );
+        // Without any font on the system we can still lay out, but nothing is drawn
+        let typeface = font_manager().typeface(value).unwrap_or_default();
+        let mut font = skia_safe::Font::new(typeface, value.size());
         font.set_edging(skia_safe::font::Edging::SubpixelAntiAlias);
         font.set_subpixel(true);
         font
@@ -115,6 +363,24 @@ impl Canvas for SkiaCanvas {
         self.surface.canvas().save();
     }
 
+    fn save_layer(&mut self, layer: &Layer) {
+        let mut paint = skia_safe::Paint::default();
+        paint
+            .set_alpha_f(layer.alpha())
+            .set_blend_mode(layer.blend_mode().into())
+            .set_image_filter(layer.image_filter().and_then(skia_image_filter));
+        let bounds = layer.bounds().map(skia_safe::Rect::from);
+        let backdrop = layer.backdrop().and_then(skia_image_filter);
+        let mut layer_rec = skia_safe::canvas::SaveLayerRec::default().paint(&paint);
+        if let Some(bounds) = &bounds {
+            layer_rec = layer_rec.bounds(bounds);
+        }
+        if let Some(backdrop) = &backdrop {
+            layer_rec = layer_rec.backdrop(backdrop);
+        }
+        self.surface.canvas().save_layer(&layer_rec);
+    }
+
     fn restore(&mut self) {
         self.surface.canvas().restore();
     }
@@ -126,6 +392,18 @@ impl Canvas for SkiaCanvas {
         self.surface.canvas().scale((size.width, size.height));
     }
 
+    fn rotate(&mut self, degrees: f32) {
+        self.surface.canvas().rotate(degrees, None);
+    }
+
+    fn skew(&mut self, x: f32, y: f32) {
+        self.surface.canvas().skew((x, y));
+    }
+
+    fn concat(&mut self, transform: &Transform) {
+        self.surface.canvas().concat(&transform.into());
+    }
+
     fn draw_rect(&mut self, rect: &Rect, paint: &Paint) {
         let rect: skia_safe::Rect = rect.into();
         self.surface.canvas().draw_rect(rect, &paint.into());
@@ -144,32 +422,203 @@ impl Canvas for SkiaCanvas {
             .draw_circle(*center, radius, &paint.into());
     }
 
-    fn draw_string(&mut self, rect: &Rect, text: &str, font: &Font, paint: &Paint) {
+    fn draw_oval(&mut self, rect: &Rect, paint: &Paint) {
         let rect: skia_safe::Rect = rect.into();
-        let rect = rect.with_offset(Point::new(0.0, rect.center_y() / 2.0));
-        self.surface.canvas().draw_str_align(
-            text,
-            rect.center(),
-            &font.into(),
+        self.surface.canvas().draw_oval(rect, &paint.into());
+    }
+
+    fn draw_arc(
+        &mut self,
+        oval: &Rect,
+        start_angle: f32,
+        sweep_angle: f32,
+        use_center: bool,
+        paint: &Paint,
+    ) {
+        let oval: skia_safe::Rect = oval.into();
+        self.surface
+            .canvas()
+            .draw_arc(oval, start_angle, sweep_angle, use_center, &paint.into());
+    }
+
+    fn draw_path(&mut self, path: &Path, paint: &Paint) {
+        self.surface.canvas().draw_path(&path.into(), &paint.into());
+    }
+
+    fn draw_shadow(&mut self, path: &Path, shadow: &Shadow) {
+        let mut paint: skia_safe::Paint = (&Paint::new(*shadow.color())).into();
+        if shadow.blur_radius() > 0.0 {
+            paint.set_mask_filter(skia_safe::MaskFilter::blur(
+                skia_safe::BlurStyle::Normal,
+                shadow.blur_sigma(),
+                false,
+            ));
+        }
+        // Stroking the outline as well grows the shape by half of the stroke width on every side
+        if shadow.spread() > 0.0 {
+            paint
+                .set_style(skia_safe::paint::Style::StrokeAndFill)
+                .set_stroke_width(shadow.spread() * 2.0)
+                .set_stroke_join(skia_safe::paint::Join::Round);
+        }
+
+        let canvas = self.surface.canvas();
+        canvas.save();
+        canvas.translate(shadow.offset());
+        canvas.draw_path(&path.into(), &paint);
+        canvas.restore();
+    }
+
+    fn draw_line(&mut self, from: &geo::Point, to: &geo::Point, paint: &Paint) {
+        self.surface.canvas().draw_line(*from, *to, &paint.into());
+    }
+
+    fn draw_polyline(&mut self, points: &[geo::Point], paint: &Paint) {
+        let points: Vec<Point> = points.iter().map(Point::from).collect();
+        self.surface.canvas().draw_points(
+            skia_safe::canvas::PointMode::Polygon,
+            &points,
             &paint.into(),
-            Align::Center,
         );
     }
 
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        position: &geo::Point,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        let paint = paint.map(skia_safe::Paint::from);
+        self.surface.canvas().draw_image_with_sampling_options(
+            image.skia_image(),
+            *position,
+            sampling,
+            paint.as_ref(),
+        );
+    }
+
+    fn draw_image_rect(
+        &mut self,
+        image: &Image,
+        src: Option<&Rect>,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        let src = src.map(skia_safe::Rect::from);
+        let paint = paint.map(skia_safe::Paint::from).unwrap_or_default();
+        self.surface.canvas().draw_image_rect_with_sampling_options(
+            image.skia_image(),
+            src.as_ref()
+                .map(|src| (src, skia_safe::canvas::SrcRectConstraint::Strict)),
+            skia_safe::Rect::from(dst),
+            sampling,
+            &paint,
+        );
+    }
+
+    fn draw_image_nine(
+        &mut self,
+        image: &Image,
+        center: &Rect,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        let paint = paint.map(skia_safe::Paint::from);
+        self.surface.canvas().draw_image_nine(
+            image.skia_image(),
+            skia_safe::Rect::from(center).round(),
+            skia_safe::Rect::from(dst),
+            filter_mode(sampling),
+            paint.as_ref(),
+        );
+    }
+
+    fn draw_image_lattice(
+        &mut self,
+        image: &Image,
+        lattice: &Lattice,
+        dst: &Rect,
+        sampling: Sampling,
+        paint: Option<&Paint>,
+    ) {
+        let x_divs: Vec<i32> = lattice.x_divs().iter().map(|div| *div as i32).collect();
+        let y_divs: Vec<i32> = lattice.y_divs().iter().map(|div| *div as i32).collect();
+        let lattice = skia_safe::canvas::Lattice {
+            x_divs: &x_divs,
+            y_divs: &y_divs,
+            rect_types: None,
+            bounds: None,
+            colors: None,
+        };
+        let paint = paint.map(skia_safe::Paint::from);
+        self.surface.canvas().draw_image_lattice(
+            image.skia_image(),
+            &lattice,
+            skia_safe::Rect::from(dst),
+            filter_mode(sampling),
+            paint.as_ref(),
+        );
+    }
+
+    fn draw_svg(&mut self, svg: &Svg, dst: &Rect, paint: Option<&Paint>) {
+        let size = svg.size();
+        let canvas = self.surface.canvas();
+        let save_count = canvas.save();
+        if let Some(paint) = paint {
+            let mut layer_paint = skia_safe::Paint::default();
+            layer_paint.set_color_filter(skia_safe::color_filters::blend(
+                skia_safe::Color4f::from(paint.color()).to_color(),
+                skia_safe::BlendMode::SrcIn,
+            ));
+            let bounds = skia_safe::Rect::from(dst);
+            canvas.save_layer(
+                &skia_safe::canvas::SaveLayerRec::default()
+                    .bounds(&bounds)
+                    .paint(&layer_paint),
+            );
+        }
+
+        // Scaling the vectors instead of a bitmap keeps the document sharp at any scale factor.
+        // A document without a size is drawn as it is into the bounds.
+        canvas.translate((dst.left(), dst.top()));
+        if size.width > 0.0 && size.height > 0.0 {
+            canvas.scale((
+                dst.size().width / size.width,
+                dst.size().height / size.height,
+            ));
+        }
+        svg.skia_dom().render(canvas);
+        canvas.restore_to_count(save_count);
+    }
+
+    fn draw_string(
+        &mut self,
+        rect: &Rect,
+        text: &str,
+        font: &Font,
+        alignment: TextAlignment,
+        paint: &Paint,
+    ) {
+        let text = Text::new(text, font.clone(), alignment.text_direction);
+        self.draw_text(&text, rect, alignment, paint)
+    }
+
     fn pixels(&mut self) -> Option<&[u8]> {
         SkiaCanvas::pixels(self)
     }
 
-    fn draw_text(&mut self, text: &Text, rect: &Rect, paint: &Paint) {
-        let rect: skia_safe::Rect = rect.into();
-        let rect = rect.with_offset(Point::new(0.0, rect.center_y() / 2.0));
-        self.surface.canvas().draw_str_align(
-            text.text(),
-            rect.center(),
-            &text.font().into(),
-            &paint.into(),
-            Align::Center,
-        );
+    fn draw_text(&mut self, text: &Text, rect: &Rect, alignment: TextAlignment, paint: &Paint) {
+        if let Some(blob) = text.blob() {
+            let origin = alignment.origin(rect, &text.metrics());
+            self.surface.canvas().draw_text_blob(
+                blob,
+                Point::new(origin.x, origin.y),
+                &paint.into(),
+            );
+        }
     }
 
     fn clip_rect(&mut self, rect: &Rect) {
@@ -177,13 +626,22 @@ impl Canvas for SkiaCanvas {
         self.surface.canvas().clip_rect(rect, None, true);
     }
 
+    fn clip_rounded_rect(&mut self, rect: &Rect, rx: f32, ry: f32) {
+        let rrect = skia_safe::RRect::new_rect_xy(skia_safe::Rect::from(rect), rx, ry);
+        self.surface.canvas().clip_rrect(rrect, None, true);
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        self.surface.canvas().clip_path(&path.into(), None, true);
+    }
+
     // fn draw_text_blob(&mut self, pos: &Point, blob: &skia_safe::TextBlob, paint: &Paint) {
     //     self.surface.canvas().draw_text_blob(blob, *pos, paint);
     // }
 
-    // fn draw_paragraph(&mut self, pos: &Point, paragraph: &skia_safe::textlayout::Paragraph) {
-    //     paragraph.paint(self.surface.canvas(), *pos);
-    // }
+    fn draw_paragraph(&mut self, pos: &geo::Point, paragraph: &Paragraph) {
+        paragraph.paint(self.surface.canvas(), pos.into());
+    }
 }
 
 unsafe impl Send for SkiaCanvas {}
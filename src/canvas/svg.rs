@@ -0,0 +1,276 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    thread,
+};
+
+use crate::geo::Size;
+
+use super::{
+    image::ImageError,
+    image_cache::{ImageSource, Load, SourceKey},
+};
+
+static SVG_CACHE: OnceLock<Mutex<SvgCache>> = OnceLock::new();
+
+/// The cache of parsed SVG documents that is shared by all windows.
+pub fn svg_cache() -> MutexGuard<'static, SvgCache> {
+    SVG_CACHE
+        .get_or_init(|| Mutex::new(SvgCache::new()))
+        .lock()
+        .expect("SVG cache lock poisoned")
+}
+
+/// Loads the document on a background thread and keeps it in the cache. The cache isn't locked
+/// while the document is read and parsed. Loads of the same document share a thread.
+pub fn load_svg_in_background(source: &ImageSource) -> SvgLoad {
+    let key = source.key();
+    let load = {
+        let mut cache = svg_cache();
+        if let Some(svg) = cache.documents.get(&key) {
+            return SvgLoad::finished(Ok(svg.clone()));
+        }
+        if let Some(load) = cache.pending.get(&key) {
+            return load.clone();
+        }
+        let load = SvgLoad::default();
+        cache.pending.insert(key.clone(), load.clone());
+        load
+    };
+
+    let source = source.clone();
+    let thread_load = load.clone();
+    thread::spawn(move || {
+        let result = Svg::from_source(&source);
+        {
+            let mut cache = svg_cache();
+            cache.pending.remove(&key);
+            if let Ok(svg) = &result {
+                cache.documents.insert(key, svg.clone());
+            }
+        }
+        thread_load.finish(result);
+    });
+
+    load
+}
+
+/// An SVG document that is loaded by `load_svg_in_background`.
+pub type SvgLoad = Load<Svg>;
+
+// Without a size or a view box the document gets the default size of browsers.
+const DEFAULT_SIZE: Size = Size {
+    width: 300.0,
+    height: 150.0,
+};
+
+/// A parsed SVG document. It stays a vector drawing, so it is rasterized by the canvas it is
+/// drawn on and is sharp at any scale. Cloning a document doesn't copy it, documents are equal
+/// when they are clones of each other.
+#[derive(Clone)]
+pub struct Svg {
+    dom: Arc<skia_safe::svg::Dom>,
+    size: Size,
+}
+
+impl Svg {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        let size = std::str::from_utf8(bytes)
+            .ok()
+            .and_then(intrinsic_size)
+            .unwrap_or(DEFAULT_SIZE);
+        let mut dom = skia_safe::svg::Dom::from_bytes(bytes).map_err(|_| ImageError::Decode)?;
+        dom.set_container_size((size.width, size.height));
+        Ok(Self {
+            dom: Arc::new(dom),
+            size,
+        })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    fn from_source(source: &ImageSource) -> Result<Self, ImageError> {
+        match source {
+            ImageSource::File(path) => Self::from_file(path),
+            ImageSource::Bytes(_, bytes) => Self::from_bytes(bytes),
+        }
+    }
+
+    /// The size the document declares with its width and height, or else with its view box.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub(crate) fn skia_dom(&self) -> &skia_safe::svg::Dom {
+        &self.dom
+    }
+}
+
+impl PartialEq for Svg {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.dom, &other.dom)
+    }
+}
+
+impl fmt::Debug for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Svg").field("size", &self.size).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Svg {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut svg = serializer.serialize_struct("Svg", 1)?;
+        svg.serialize_field("size", &self.size)?;
+        svg.end()
+    }
+}
+
+// The size from the attributes of the root element, skia doesn't expose it.
+fn intrinsic_size(svg: &str) -> Option<Size> {
+    let start = svg.find("<svg")?;
+    let tag = &svg[start..start + svg[start..].find('>')?];
+    let length = |name| attribute(tag, name).and_then(parse_length);
+    let view_box = attribute(tag, "viewBox").and_then(|view_box| {
+        let values: Vec<f32> = view_box
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|value| !value.is_empty())
+            .filter_map(|value| value.parse().ok())
+            .collect();
+        // An empty view box would scale the document by infinity
+        (values.len() == 4
+            && values[2..]
+                .iter()
+                .all(|value| value.is_finite() && *value > 0.0))
+        .then(|| Size::new(values[2], values[3]))
+    });
+
+    match (length("width"), length("height"), view_box) {
+        (Some(width), Some(height), _) => Some(Size::new(width, height)),
+        // A missing side follows the aspect ratio of the view box
+        (Some(width), None, Some(view_box)) => {
+            Some(Size::new(width, width * view_box.height / view_box.width))
+        }
+        (None, Some(height), Some(view_box)) => {
+            Some(Size::new(height * view_box.width / view_box.height, height))
+        }
+        (_, _, view_box) => view_box,
+    }
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut offset = 0;
+    while let Some(index) = tag[offset..].find(name) {
+        let index = offset + index;
+        offset = index + name.len();
+        // Skip attributes that end with the name, like stroke-width
+        if !tag[..index].ends_with(char::is_whitespace) {
+            continue;
+        }
+
+        let value = tag[offset..].trim_start().strip_prefix('=')?.trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &value[1..];
+        return value.find(quote).map(|end| &value[..end]);
+    }
+    None
+}
+
+// Only plain numbers and pixels, percentages depend on a container we don't know yet.
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .parse()
+        .ok()
+        .filter(|length: &f32| *length > 0.0)
+}
+
+/// Keeps parsed SVG documents so that each is parsed once. Icon sets are small, so documents
+/// are kept until they are removed. Documents that failed to load are tried again the next time.
+pub struct SvgCache {
+    documents: HashMap<SourceKey, Svg>,
+    // Documents that are loading on a background thread
+    pending: HashMap<SourceKey, SvgLoad>,
+}
+
+impl SvgCache {
+    pub fn new() -> Self {
+        Self {
+            documents: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// The document from the cache, or loaded from the source and kept.
+    /// The cache stays locked while the document is parsed, widgets use `load_svg_in_background`.
+    pub fn load(&mut self, source: &ImageSource) -> Result<Svg, ImageError> {
+        let key = source.key();
+        if let Some(svg) = self.documents.get(&key) {
+            return Ok(svg.clone());
+        }
+
+        let svg = Svg::from_source(source)?;
+        self.documents.insert(key, svg.clone());
+        Ok(svg)
+    }
+
+    pub fn remove(&mut self, source: &ImageSource) {
+        self.documents.remove(&source.key());
+    }
+
+    pub fn clear(&mut self) {
+        self.documents.clear();
+    }
+}
+
+impl Default for SvgCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::intrinsic_size;
+    use crate::geo::Size;
+
+    #[test]
+    fn size_from_width_and_height() {
+        let svg =
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="24px" height='16' stroke-width="2">"#;
+        assert_eq!(intrinsic_size(svg), Some(Size::new(24.0, 16.0)));
+    }
+
+    #[test]
+    fn missing_side_follows_the_view_box() {
+        let svg = r#"<svg width="48" viewBox="0 0 24 12"></svg>"#;
+        assert_eq!(intrinsic_size(svg), Some(Size::new(48.0, 24.0)));
+
+        let svg = r#"<svg viewBox="0,0,24,12"></svg>"#;
+        assert_eq!(intrinsic_size(svg), Some(Size::new(24.0, 12.0)));
+    }
+
+    #[test]
+    fn empty_view_boxes_are_ignored() {
+        let svg = r#"<svg viewBox="0 0 0 12"></svg>"#;
+        assert_eq!(intrinsic_size(svg), None);
+
+        let svg = r#"<svg width="48" viewBox="0 0 -24 12"></svg>"#;
+        assert_eq!(intrinsic_size(svg), None);
+    }
+
+    #[test]
+    fn percentages_are_unknown() {
+        let svg = r#"<svg width="100%" height="100%"></svg>"#;
+        assert_eq!(intrinsic_size(svg), None);
+    }
+}
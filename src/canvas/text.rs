@@ -1,33 +1,102 @@
-use skia_safe::TextBlob;
+use skia_safe::{
+    shaper::run_handler::{Buffer, RunHandler, RunInfo},
+    GlyphId, TextBlob, TextBlobBuilder,
+};
 
-use crate::geo::{Point, Rect, Size};
+use crate::{
+    geo::{Point, Rect, Size},
+    widget::style::{HorizontalAlignment, TextDirection, VerticalAlignment},
+};
 
-use super::font::Font;
+use super::{font::Font, font_manager::font_manager, text_measurer::TextMetrics};
 
+/// A single line of shaped text.
+/// Shaping falls back to other fonts for characters the font doesn't have, and reorders
+/// mixed left-to-right and right-to-left text around the base direction it is given.
+/// Measuring and drawing both use the shaped result.
 pub struct Text {
     text: String,
-    blob: TextBlob,
+    blob: Option<TextBlob>,
     font: Font,
+    text_direction: TextDirection,
+    width: f32,
+    ascent: f32,
+    descent: f32,
+    cap_height: f32,
+    font_ascent: f32,
 }
 
 impl Text {
-    pub fn new(text: &str, font: Font) -> Self {
-        let blob = skia_safe::TextBlob::new(text, &(&font).into()).unwrap();
+    /// Shapes the text with the direction of the widget that shows it as its base direction.
+    pub fn new(text: &str, font: Font, text_direction: TextDirection) -> Self {
+        let skia_font: skia_safe::Font = (&font).into();
+        let (_, metrics) = skia_font.metrics();
+        let mut runs = ShapedRuns {
+            builder: TextBlobBuilder::new(),
+            glyphs: Vec::new(),
+            positions: Vec::new(),
+            advance: 0.0,
+            ascent: -metrics.ascent,
+            descent: metrics.descent,
+        };
+        font_manager().shaper().shape(
+            text,
+            &skia_font,
+            !text_direction.is_right_to_left(),
+            f32::INFINITY,
+            &mut runs,
+        );
+
         Self {
             text: text.into(),
-            blob,
+            blob: runs.builder.make(),
             font,
+            text_direction,
+            width: runs.advance,
+            ascent: runs.ascent,
+            descent: runs.descent,
+            cap_height: metrics.cap_height,
+            font_ascent: -metrics.ascent,
         }
     }
 
+    /// The box of the text relative to its baseline, as wide as the advance of the text
+    /// and as high as the ascent and descent of the fonts that are used.
     pub fn bounds(&self) -> Rect {
-        let bounds = self.blob.bounds();
         Rect::new(
-            Point::new(bounds.left(), bounds.top()),
-            Size::new(bounds.width(), bounds.height()),
+            Point::new(0.0, -self.ascent),
+            Size::new(self.width, self.ascent + self.descent),
         )
     }
 
+    pub fn size(&self) -> Size {
+        self.metrics().size()
+    }
+
+    pub fn metrics(&self) -> TextMetrics {
+        TextMetrics {
+            width: self.width,
+            ascent: self.ascent,
+            descent: self.descent,
+            cap_height: self.cap_height,
+            font_ascent: self.font_ascent,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Distance from the top of the text to the baseline.
+    pub fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    /// Distance from the baseline to the bottom of the text.
+    pub fn descent(&self) -> f32 {
+        self.descent
+    }
+
     pub fn text(&self) -> &str {
         &self.text
     }
@@ -35,4 +104,105 @@ impl Text {
     pub fn font(&self) -> &Font {
         &self.font
     }
+
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction
+    }
+
+    /// The shaped glyphs, positioned with the start of the baseline at the origin.
+    /// `None` when there's nothing to draw.
+    pub(crate) fn blob(&self) -> Option<&TextBlob> {
+        self.blob.as_ref()
+    }
+}
+
+/// Where text is placed inside the rect it is drawn in.
+/// `Start` and `End` are resolved with the text direction of the alignment.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TextAlignment {
+    pub horizontal: HorizontalAlignment,
+    pub vertical: VerticalAlignment,
+    pub text_direction: TextDirection,
+}
+
+impl TextAlignment {
+    pub fn new(horizontal: HorizontalAlignment, vertical: VerticalAlignment) -> Self {
+        Self {
+            horizontal,
+            vertical,
+            text_direction: TextDirection::default(),
+        }
+    }
+
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
+    /// The position of the start of the baseline of text with the metrics inside the rect.
+    pub fn origin(&self, rect: &Rect, metrics: &TextMetrics) -> Point {
+        let size = rect.size();
+        let x = match self.horizontal.resolve(self.text_direction) {
+            HorizontalAlignment::Center => (size.width - metrics.width) / 2.0,
+            HorizontalAlignment::Right => size.width - metrics.width,
+            _ => 0.0,
+        };
+        let y = match self.vertical {
+            VerticalAlignment::Top => metrics.ascent,
+            VerticalAlignment::Center => (size.height + metrics.cap_height) / 2.0,
+            VerticalAlignment::Baseline => metrics.font_ascent,
+            VerticalAlignment::Bottom => size.height - metrics.descent,
+        };
+
+        Point::new(rect.left() + x, rect.top() + y)
+    }
+}
+
+// Collects the runs that the shaper produces into a text blob and keeps track of the advance and
+// the vertical metrics of all fonts that are used, including fallbacks.
+struct ShapedRuns {
+    builder: TextBlobBuilder,
+    glyphs: Vec<GlyphId>,
+    positions: Vec<skia_safe::Point>,
+    advance: f32,
+    ascent: f32,
+    descent: f32,
+}
+
+impl RunHandler for ShapedRuns {
+    fn begin_line(&mut self) {}
+
+    fn run_info(&mut self, info: &RunInfo) {
+        let (_, metrics) = info.font.metrics();
+        self.ascent = self.ascent.max(-metrics.ascent);
+        self.descent = self.descent.max(metrics.descent);
+    }
+
+    fn commit_run_info(&mut self) {}
+
+    fn run_buffer(&mut self, info: &RunInfo) -> Buffer {
+        self.glyphs.clear();
+        self.glyphs.resize(info.glyph_count, 0);
+        self.positions.clear();
+        self.positions
+            .resize(info.glyph_count, skia_safe::Point::default());
+        // Runs arrive in visual order, so every run starts where the previous one ended
+        Buffer::new(
+            &mut self.glyphs,
+            &mut self.positions,
+            skia_safe::Point::new(self.advance, 0.0),
+        )
+    }
+
+    fn commit_run_buffer(&mut self, info: &RunInfo) {
+        let (glyphs, positions) = self
+            .builder
+            .alloc_run_pos(info.font, info.glyph_count, None);
+        glyphs.copy_from_slice(&self.glyphs);
+        positions.copy_from_slice(&self.positions);
+        self.advance += info.advance.x;
+    }
+
+    fn commit_line(&mut self) {}
 }
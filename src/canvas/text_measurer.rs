@@ -0,0 +1,144 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use crate::{geo::Size, widget::style::TextDirection};
+
+use super::{
+    font::Font,
+    paragraph::{Paragraph, ParagraphStyle},
+    text::Text,
+};
+
+/// The size of a single line of text.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextMetrics {
+    pub width: f32,
+    /// Distance from the top of the text to the baseline.
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the text.
+    pub descent: f32,
+    /// Height of capital letters above the baseline.
+    pub cap_height: f32,
+    /// Ascent of the font itself, the ascent of the text is larger when fallback fonts are taller.
+    pub font_ascent: f32,
+}
+
+impl TextMetrics {
+    pub fn size(&self) -> Size {
+        Size::new(self.width.ceil(), (self.ascent + self.descent).ceil())
+    }
+}
+
+/// Measures text for widgets while they are sized and laid out, and lays out the paragraphs
+/// painters draw. Implement it to size widgets without a font backend, in tests for example.
+pub trait TextMeasurer {
+    /// Measures the text as a single line, shaped with the direction as its base direction.
+    fn measure(&self, text: &str, font: &Font, text_direction: TextDirection) -> TextMetrics;
+
+    /// The text as a paragraph that is laid out for the width. By default it is shaped
+    /// like `ShapingTextMeasurer` does, so that it wraps the way it is painted.
+    fn layout_paragraph(
+        &self,
+        text: &str,
+        font: &Font,
+        style: &ParagraphStyle,
+        max_width: f32,
+    ) -> Paragraph {
+        ShapingTextMeasurer.layout_paragraph(text, font, style, max_width)
+    }
+
+    /// Measures text that wraps to the width, as wide as its longest line.
+    /// By default it is the size of the paragraph from `layout_paragraph`.
+    fn measure_paragraph(
+        &self,
+        text: &str,
+        font: &Font,
+        style: &ParagraphStyle,
+        max_width: f32,
+    ) -> Size {
+        self.layout_paragraph(text, font, style, max_width).size()
+    }
+}
+
+// Measures by shaping, without a cache. Used where no measurer was given.
+pub(crate) struct ShapingTextMeasurer;
+
+impl TextMeasurer for ShapingTextMeasurer {
+    fn measure(&self, text: &str, font: &Font, text_direction: TextDirection) -> TextMetrics {
+        Text::new(text, font.clone(), text_direction).metrics()
+    }
+
+    fn layout_paragraph(
+        &self,
+        text: &str,
+        font: &Font,
+        style: &ParagraphStyle,
+        max_width: f32,
+    ) -> Paragraph {
+        let paragraph = Paragraph::new(text, font.clone(), style.clone());
+        paragraph.layout(max_width);
+        paragraph
+    }
+}
+
+// Measurements are cheap to keep, but the cache is cleared once it holds this many to bound its memory.
+const MAX_CACHED: usize = 4096;
+
+// Paragraphs by text, font and width. Styles can't be hashed, the few styles are compared one by one.
+type ParagraphCache = HashMap<(String, Font, u32), Vec<(ParagraphStyle, Size)>>;
+
+/// Measures text the way it is painted, by shaping lines into a `Text` and laying out paragraphs.
+/// Results are cached by text, font and direction, and for paragraphs by their style and width too.
+#[derive(Default)]
+pub struct SkiaTextMeasurer {
+    cache: RefCell<HashMap<(String, Font, TextDirection), TextMetrics>>,
+    paragraphs: RefCell<ParagraphCache>,
+}
+
+impl SkiaTextMeasurer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TextMeasurer for SkiaTextMeasurer {
+    fn measure(&self, text: &str, font: &Font, text_direction: TextDirection) -> TextMetrics {
+        let key = (text.to_string(), font.clone(), text_direction);
+        if let Some(metrics) = self.cache.borrow().get(&key) {
+            return *metrics;
+        }
+
+        let metrics = ShapingTextMeasurer.measure(text, font, text_direction);
+        let mut cache = self.cache.borrow_mut();
+        if cache.len() >= MAX_CACHED {
+            cache.clear();
+        }
+        cache.insert(key, metrics);
+        metrics
+    }
+
+    fn measure_paragraph(
+        &self,
+        text: &str,
+        font: &Font,
+        style: &ParagraphStyle,
+        max_width: f32,
+    ) -> Size {
+        let key = (text.to_string(), font.clone(), max_width.to_bits());
+        if let Some(styles) = self.paragraphs.borrow().get(&key) {
+            if let Some((_, size)) = styles.iter().find(|(cached, _)| cached == style) {
+                return *size;
+            }
+        }
+
+        let size = ShapingTextMeasurer.measure_paragraph(text, font, style, max_width);
+        let mut paragraphs = self.paragraphs.borrow_mut();
+        if paragraphs.len() >= MAX_CACHED {
+            paragraphs.clear();
+        }
+        paragraphs
+            .entry(key)
+            .or_default()
+            .push((style.clone(), size));
+        size
+    }
+}
@@ -4,13 +4,15 @@ use crate::{
     animation::{
         animation_event::AnimationEvent, animation_request::AnimationRequest, AnimationId,
     },
-    app::event::MouseEvent,
+    app::event::{KeyEvent, MouseEvent},
+    geo::Point,
     user_interface::{ui_state::UIState, value::Var},
     widget::{ui_message::UIMessage, Widget},
 };
 
 pub enum UIEvent<'a> {
     Mouse(&'a MouseEvent),
+    Key(&'a KeyEvent),
     Animation(&'a AnimationEvent),
     Binding(&'a str),
     Internal(&'a UIMessage),
@@ -24,6 +26,7 @@ pub struct EventCtx<'a> {
     pub drag_data: Option<Box<dyn Any>>,
     pub drag_widget: Option<Box<dyn Widget>>,
     animation_requests: Vec<AnimationRequest>,
+    consumed_scroll: Point,
 }
 
 impl<'a> EventCtx<'a> {
@@ -34,6 +37,7 @@ impl<'a> EventCtx<'a> {
             drag_data: None,
             drag_widget: None,
             animation_requests: Vec::new(),
+            consumed_scroll: Point::default(),
         }
     }
 
@@ -49,6 +53,13 @@ impl<'a> EventCtx<'a> {
         }
     }
 
+    pub fn key_event(&self) -> &'a KeyEvent {
+        match self.event {
+            UIEvent::Key(event) => event,
+            _ => panic!("Event is not a key event"),
+        }
+    }
+
     pub fn request_widget_animation(&mut self, animation_id: AnimationId, duration: Duration) {
         self.animation_requests
             .push(AnimationRequest::Widget(animation_id, duration));
@@ -66,12 +77,25 @@ impl<'a> EventCtx<'a> {
         }
     }
 
+    /// Marks part of the scroll delta of a scroll event as used, the rest is passed on to the ancestors.
+    pub fn consume_scroll(&mut self, delta: Point) {
+        self.consumed_scroll = self.consumed_scroll + delta;
+    }
+
+    pub fn consumed_scroll(&self) -> Point {
+        self.consumed_scroll
+    }
+
     pub fn drag_data<T: 'static>(&mut self) -> Option<&T> {
         self.drag_data
             .as_ref()
             .map(|any| any.downcast_ref::<T>().unwrap())
     }
 
+    pub fn ui_state(&self) -> &UIState {
+        self.ui_state
+    }
+
     pub fn binding(&self) -> Option<&Var> {
         match self.event {
             UIEvent::Binding(binding) => self.ui_state.get(binding),
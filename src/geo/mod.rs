@@ -1,7 +1,9 @@
 mod point;
 mod rect;
 mod size;
+mod transform;
 
 pub use point::Point;
 pub use rect::Rect;
 pub use size::Size;
+pub use transform::Transform;
@@ -2,7 +2,8 @@ use std::ops::{Add, Sub};
 
 use super::Size;
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Point {
     pub x: f32,
     pub y: f32,
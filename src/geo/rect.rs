@@ -1,6 +1,7 @@
 use super::{Point, Size};
 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Rect {
     left: f32,
     right: f32,
@@ -42,6 +43,22 @@ impl Rect {
         }
     }
 
+    pub fn left(&self) -> f32 {
+        self.left
+    }
+
+    pub fn right(&self) -> f32 {
+        self.right
+    }
+
+    pub fn top(&self) -> f32 {
+        self.top
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.bottom
+    }
+
     pub fn size(&self) -> Size {
         Size {
             width: self.right - self.left,
@@ -1,6 +1,7 @@
 use std::ops::{Div, Mul};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Size {
     pub width: f32,
     pub height: f32,
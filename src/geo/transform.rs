@@ -0,0 +1,158 @@
+use std::ops::Mul;
+
+use super::Point;
+
+/// A 2D affine transform, it maps (x, y) to (a * x + c * y + e, b * x + d * y + f).
+/// Angles are in degrees, positive angles rotate clockwise because y points down.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Transform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    pub const fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub fn translation(x: f32, y: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    pub fn rotation(degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Slants x by `x` times y and y by `y` times x, the factors are the tangents of the skew angles.
+    pub fn skew(x: f32, y: f32) -> Self {
+        Self::new(1.0, y, x, 1.0, 0.0, 0.0)
+    }
+
+    /// This transform applied around the origin instead of around (0, 0).
+    pub fn around(&self, origin: Point) -> Self {
+        Self::translation(-origin.x, -origin.y)
+            .then(self)
+            .then(&Self::translation(origin.x, origin.y))
+    }
+
+    /// The transform that applies this transform first and then the other one.
+    pub fn then(&self, other: &Transform) -> Self {
+        Self::new(
+            other.a * self.a + other.c * self.b,
+            other.b * self.a + other.d * self.b,
+            other.a * self.c + other.c * self.d,
+            other.b * self.c + other.d * self.d,
+            other.a * self.e + other.c * self.f + other.e,
+            other.b * self.e + other.d * self.f + other.f,
+        )
+    }
+
+    /// The transform that undoes this one, none if it collapses the plane onto a line or a point.
+    pub fn invert(&self) -> Option<Self> {
+        let determinant = self.a * self.d - self.b * self.c;
+        if determinant.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        let a = self.d / determinant;
+        let b = -self.b / determinant;
+        let c = -self.c / determinant;
+        let d = self.a / determinant;
+        Some(Self::new(
+            a,
+            b,
+            c,
+            d,
+            -(a * self.e + c * self.f),
+            -(b * self.e + d * self.f),
+        ))
+    }
+
+    pub fn map_point(&self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.e,
+            self.b * point.x + self.d * point.y + self.f,
+        )
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// `a * b` applies `b` first and then `a`, like multiplying the matrices.
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Self::Output {
+        rhs.then(&self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transform;
+    use crate::geo::Point;
+
+    fn assert_close(a: Point, b: Point) {
+        assert!(
+            (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn then_applies_self_first() {
+        let transform = Transform::scale(2.0, 3.0).then(&Transform::translation(10.0, 20.0));
+        assert_close(
+            transform.map_point(Point::new(1.0, 1.0)),
+            Point::new(12.0, 23.0),
+        );
+
+        let transform = Transform::translation(10.0, 20.0).then(&Transform::scale(2.0, 3.0));
+        assert_close(
+            transform.map_point(Point::new(1.0, 1.0)),
+            Point::new(22.0, 63.0),
+        );
+    }
+
+    #[test]
+    fn invert_undoes_the_transform() {
+        let transform = Transform::rotation(30.0)
+            .then(&Transform::scale(2.0, 0.5))
+            .then(&Transform::translation(-4.0, 7.0));
+        let inverse = transform.invert().unwrap();
+        let point = Point::new(3.0, -5.0);
+        assert_close(inverse.map_point(transform.map_point(point)), point);
+        assert_close(
+            transform.then(&inverse).map_point(point),
+            Transform::IDENTITY.map_point(point),
+        );
+    }
+
+    #[test]
+    fn collapsing_transforms_have_no_inverse() {
+        assert_eq!(Transform::scale(0.0, 1.0).invert(), None);
+        assert_eq!(Transform::new(1.0, 2.0, 2.0, 4.0, 0.0, 0.0).invert(), None);
+    }
+}
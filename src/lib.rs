@@ -4,6 +4,7 @@ pub mod canvas;
 pub mod event_context;
 pub mod geo;
 pub mod gpu;
+pub mod localization;
 pub mod mouse_event;
 pub mod mutation;
 pub mod painter;
@@ -0,0 +1,359 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fluent_bundle::{FluentArgs, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+// The separators of numbers, and the order of the fields of dates, for the languages that differ
+// from English. Regions only matter for English dates.
+struct Conventions {
+    grouping: &'static str,
+    decimal: &'static str,
+    date: DateOrder,
+    date_separator: &'static str,
+}
+
+enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+fn conventions(locale: &LanguageIdentifier) -> Conventions {
+    let region = locale.region.as_ref().map(|region| region.as_str());
+    match locale.language.as_str() {
+        "de" | "nl" | "da" | "id" | "it" | "es" | "pt" | "tr" => Conventions {
+            grouping: ".",
+            decimal: ",",
+            date: DateOrder::DayMonthYear,
+            date_separator: if locale.language.as_str() == "de" {
+                "."
+            } else {
+                "/"
+            },
+        },
+        "fr" | "ru" | "pl" | "cs" | "sv" | "fi" | "nb" | "uk" => Conventions {
+            // Narrow no-break space
+            grouping: "\u{202F}",
+            decimal: ",",
+            date: DateOrder::DayMonthYear,
+            date_separator: if locale.language.as_str() == "fr" {
+                "/"
+            } else {
+                "."
+            },
+        },
+        "ja" | "zh" | "ko" => Conventions {
+            grouping: ",",
+            decimal: ".",
+            date: DateOrder::YearMonthDay,
+            date_separator: "/",
+        },
+        "en" if region != Some("US") => Conventions {
+            grouping: ",",
+            decimal: ".",
+            date: DateOrder::DayMonthYear,
+            date_separator: "/",
+        },
+        _ => Conventions {
+            grouping: ",",
+            decimal: ".",
+            date: DateOrder::MonthDayYear,
+            date_separator: "/",
+        },
+    }
+}
+
+/// Formats the number with the grouping and decimal separators of the locale,
+/// rounded to the given number of fraction digits.
+pub fn format_number(locale: &LanguageIdentifier, value: f64, fraction_digits: usize) -> String {
+    let conventions = conventions(locale);
+    let formatted = format!("{:.*}", fraction_digits, value.abs());
+    let (integer, fraction) = match formatted.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut result = String::new();
+    if value.is_sign_negative() && formatted.chars().any(|c| c != '0' && c != '.') {
+        result.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            result.push_str(conventions.grouping);
+        }
+        result.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        result.push_str(conventions.decimal);
+        result.push_str(fraction);
+    }
+
+    result
+}
+
+/// A day in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+}
+
+/// The date of the time in UTC.
+impl From<SystemTime> for Date {
+    fn from(time: SystemTime) -> Self {
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            // Rounded down, so that a fraction of a second before the epoch is the day before
+            Err(error) => {
+                let duration = error.duration();
+                -(duration.as_secs() as i64) - i64::from(duration.subsec_nanos() > 0)
+            }
+        };
+
+        // Converts days since 1970-01-01 to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+        let days = seconds.div_euclid(86_400) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self::new(year as i32, month as u8, day as u8)
+    }
+}
+
+/// Formats the date numerically in the order and with the separator of the locale.
+pub fn format_date(locale: &LanguageIdentifier, date: Date) -> String {
+    let conventions = conventions(locale);
+    let separator = conventions.date_separator;
+    let (year, month, day) = (
+        date.year.to_string(),
+        format!("{:02}", date.month),
+        format!("{:02}", date.day),
+    );
+
+    let fields = match conventions.date {
+        DateOrder::DayMonthYear => [day, month, year],
+        DateOrder::MonthDayYear => [month, day, year],
+        DateOrder::YearMonthDay => [year, month, day],
+    };
+
+    fields.join(separator)
+}
+
+// The most fraction digits `NUMBER` formats with, as in `Intl.NumberFormat`.
+const MAX_FRACTION_DIGITS: usize = 20;
+
+/// The `NUMBER` function of the catalogs, for example `{ NUMBER($total, maximumFractionDigits: 2) }`.
+/// It formats with `format_number`, with as few fraction digits as the value needs between
+/// `minimumFractionDigits` (0 by default) and `maximumFractionDigits` (3 by default),
+/// both at most 20.
+/// The result is text, plural variants select on the argument itself: `{ $total -> ... }`.
+pub(crate) fn number_function<'a>(
+    locale: &LanguageIdentifier,
+    positional: &[FluentValue<'a>],
+    named: &FluentArgs,
+) -> FluentValue<'a> {
+    let (value, options) = match positional.first() {
+        Some(FluentValue::Number(number)) => (number.value, Some(&number.options)),
+        Some(FluentValue::String(text)) => match text.trim().parse() {
+            Ok(value) => (value, None),
+            Err(_) => return FluentValue::Error,
+        },
+        _ => return FluentValue::Error,
+    };
+
+    let option = |name: &str, default: usize| {
+        let digits = match named.get(name) {
+            Some(FluentValue::Number(number)) if number.value >= 0.0 => number.value as usize,
+            _ => default,
+        };
+        digits.min(MAX_FRACTION_DIGITS)
+    };
+    let minimum = option(
+        "minimumFractionDigits",
+        options
+            .and_then(|options| options.minimum_fraction_digits)
+            .unwrap_or(0),
+    );
+    let maximum = option(
+        "maximumFractionDigits",
+        options
+            .and_then(|options| options.maximum_fraction_digits)
+            .unwrap_or(3),
+    )
+    .max(minimum);
+
+    // The fewest digits that round to the same value as the maximum
+    let rounded = |digits: usize| {
+        let factor = 10f64.powi(digits as i32);
+        (value * factor).round() / factor
+    };
+    let digits = (minimum..=maximum)
+        .find(|digits| rounded(*digits) == rounded(maximum))
+        .unwrap_or(maximum);
+
+    FluentValue::from(format_number(locale, value, digits))
+}
+
+/// The `DATE` function of the catalogs, for example `{ DATE($due) }`. It formats with `format_date`
+/// a date given as `YYYY-MM-DD`, or as a number of seconds since the Unix epoch.
+pub(crate) fn date_function<'a>(
+    locale: &LanguageIdentifier,
+    positional: &[FluentValue<'a>],
+    _named: &FluentArgs,
+) -> FluentValue<'a> {
+    let date = match positional.first() {
+        // Seconds that aren't finite or don't fit in a time are an error, not a panic
+        Some(FluentValue::Number(number)) => Duration::try_from_secs_f64(number.value.abs())
+            .ok()
+            .and_then(|duration| {
+                if number.value >= 0.0 {
+                    UNIX_EPOCH.checked_add(duration)
+                } else {
+                    UNIX_EPOCH.checked_sub(duration)
+                }
+            })
+            .map(Date::from),
+        Some(FluentValue::String(text)) => parse_date(text),
+        _ => None,
+    };
+
+    match date {
+        Some(date) => FluentValue::from(format_date(locale, date)),
+        None => FluentValue::Error,
+    }
+}
+
+fn parse_date(text: &str) -> Option<Date> {
+    let mut fields = text.trim().splitn(3, '-');
+    let year = fields.next()?.parse().ok()?;
+    let month = fields
+        .next()?
+        .parse()
+        .ok()
+        .filter(|month| (1..=12).contains(month))?;
+    let day = fields
+        .next()?
+        .parse()
+        .ok()
+        .filter(|day| (1..=31).contains(day))?;
+    Some(Date::new(year, month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use fluent_bundle::{FluentArgs, FluentValue};
+    use unic_langid::LanguageIdentifier;
+
+    use super::{date_function, format_date, format_number, number_function, parse_date, Date};
+
+    fn locale(locale: &str) -> LanguageIdentifier {
+        locale.parse().unwrap()
+    }
+
+    fn text(value: FluentValue) -> Option<String> {
+        match value {
+            FluentValue::String(text) => Some(text.into_owned()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn numbers_use_the_separators_of_the_locale() {
+        assert_eq!(
+            format_number(&locale("en-US"), 1234567.891, 2),
+            "1,234,567.89"
+        );
+        assert_eq!(format_number(&locale("de"), 1234567.891, 2), "1.234.567,89");
+        assert_eq!(format_number(&locale("fr"), 1234.5, 1), "1\u{202F}234,5");
+        assert_eq!(format_number(&locale("en"), -1234.0, 0), "-1,234");
+        assert_eq!(format_number(&locale("en"), -0.001, 2), "0.00");
+    }
+
+    #[test]
+    fn dates_use_the_order_and_separator_of_the_locale() {
+        let date = Date::new(2024, 3, 5);
+        assert_eq!(format_date(&locale("en-US"), date), "03/05/2024");
+        assert_eq!(format_date(&locale("en-GB"), date), "05/03/2024");
+        assert_eq!(format_date(&locale("de-AT"), date), "05.03.2024");
+        assert_eq!(format_date(&locale("ja"), date), "2024/03/05");
+    }
+
+    #[test]
+    fn dates_of_times_are_in_utc() {
+        assert_eq!(Date::from(UNIX_EPOCH), Date::new(1970, 1, 1));
+        assert_eq!(
+            Date::from(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            Date::new(2000, 2, 29)
+        );
+        assert_eq!(
+            Date::from(UNIX_EPOCH - Duration::from_millis(500)),
+            Date::new(1969, 12, 31)
+        );
+    }
+
+    #[test]
+    fn only_valid_dates_are_parsed() {
+        assert_eq!(parse_date(" 2024-02-29 "), Some(Date::new(2024, 2, 29)));
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("2024-01-32"), None);
+        assert_eq!(parse_date("2024-01"), None);
+        assert_eq!(parse_date("tomorrow"), None);
+    }
+
+    #[test]
+    fn number_uses_the_fewest_fraction_digits_that_are_needed() {
+        let en = locale("en");
+        let number = |value: f64, args: &FluentArgs| {
+            text(number_function(&en, &[FluentValue::from(value)], args))
+        };
+        assert_eq!(number(1.5, &FluentArgs::new()), Some("1.5".into()));
+        assert_eq!(number(1.23456, &FluentArgs::new()), Some("1.235".into()));
+
+        let mut args = FluentArgs::new();
+        args.set("minimumFractionDigits", 2);
+        assert_eq!(number(1.5, &args), Some("1.50".into()));
+
+        let mut args = FluentArgs::new();
+        args.set("maximumFractionDigits", 1000);
+        let formatted = number(1.0 / 3.0, &args).unwrap();
+        assert!(formatted.len() <= "0.".len() + 20);
+    }
+
+    #[test]
+    fn date_accepts_dates_and_seconds_that_fit_in_a_time() {
+        let en = locale("en-US");
+        let date =
+            |value: FluentValue<'static>| text(date_function(&en, &[value], &FluentArgs::new()));
+        assert_eq!(
+            date(FluentValue::from("2024-03-05")),
+            Some("03/05/2024".into())
+        );
+        assert_eq!(date(FluentValue::from(86_400.0)), Some("01/02/1970".into()));
+        assert_eq!(
+            date(FluentValue::from(-86_400.0)),
+            Some("12/31/1969".into())
+        );
+        assert_eq!(date(FluentValue::from(f64::INFINITY)), None);
+        assert_eq!(date(FluentValue::from(f64::NAN)), None);
+        assert_eq!(date(FluentValue::from(1e300)), None);
+    }
+}
@@ -0,0 +1,366 @@
+pub mod format;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+pub use unic_langid::LanguageIdentifier;
+
+use crate::user_interface::{ui_state::UIState, value::Var};
+
+/// The binding that holds the locale of the user interface, as a string like "en-US".
+/// Setting it re-renders every widget that shows localized text.
+pub const LOCALE: &str = "locale";
+
+static LOCALIZER: OnceLock<Mutex<Localizer>> = OnceLock::new();
+
+/// The localizer that is shared by all windows.
+pub fn localizer() -> MutexGuard<'static, Localizer> {
+    LOCALIZER
+        .get_or_init(|| Mutex::new(Localizer::new()))
+        .lock()
+        .expect("Localizer lock poisoned")
+}
+
+/// Formats the message with the shared localizer, see `Localizer::format`. The problems it finds are
+/// passed to the diagnostics handler after the localizer is unlocked, so the handler may use it too.
+pub fn localize(locale: &LanguageIdentifier, key: &str, args: &[(&str, &Var)]) -> String {
+    let (text, diagnostics, handler) = {
+        let mut localizer = localizer();
+        let text = localizer.format(locale, key, args);
+        match localizer.diagnostics_handler.clone() {
+            Some(handler) => (text, localizer.take_diagnostics(), Some(handler)),
+            None => (text, Vec::new(), None),
+        }
+    };
+
+    if let Some(handler) = handler {
+        for error in &diagnostics {
+            (handler)(error)
+        }
+    }
+    text
+}
+
+/// The locale in the `LOCALE` binding, or the fallback locale when it isn't set or can't be parsed.
+pub fn current_locale(ui_state: &UIState) -> LanguageIdentifier {
+    ui_state
+        .get(LOCALE)
+        .and_then(|locale| locale.to_string().parse().ok())
+        .unwrap_or_else(|| localizer().fallback_locale().clone())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LocalizationError {
+    Io(String),
+    /// The catalog has syntax errors, the messages that could be parsed are still added.
+    Parse {
+        locale: String,
+        errors: Vec<String>,
+    },
+    /// The key isn't in the catalog of the locale or of the fallback locale.
+    MissingMessage {
+        locale: String,
+        key: String,
+    },
+    /// The message was found but formatting it failed, for example because an argument is missing.
+    Format {
+        locale: String,
+        key: String,
+        errors: Vec<String>,
+    },
+}
+
+impl Display for LocalizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalizationError::Io(error) => write!(f, "Reading catalog failed: {}", error),
+            LocalizationError::Parse { locale, errors } => {
+                write!(
+                    f,
+                    "Catalog for '{}' is invalid: {}",
+                    locale,
+                    errors.join(", ")
+                )
+            }
+            LocalizationError::MissingMessage { locale, key } => {
+                write!(f, "No message '{}' for '{}'", key, locale)
+            }
+            LocalizationError::Format {
+                locale,
+                key,
+                errors,
+            } => write!(
+                f,
+                "Formatting '{}' for '{}' failed: {}",
+                key,
+                locale,
+                errors.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LocalizationError {}
+
+type DiagnosticsHandler = Arc<dyn Fn(&LocalizationError) + Send + Sync>;
+
+/// Looks up messages by key in Fluent catalogs, one set of catalogs per locale.
+/// Messages that are missing for a locale are taken from the fallback locale, and if they're
+/// missing there too the key itself is shown. Every problem is collected once, until `take_diagnostics`
+/// is called or until `localize` passes it to the diagnostics handler.
+/// Messages format numbers and dates for their locale with the `NUMBER` and `DATE` functions.
+pub struct Localizer {
+    bundles: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+    fallback_locale: LanguageIdentifier,
+    reported: HashSet<LocalizationError>,
+    diagnostics: Vec<LocalizationError>,
+    diagnostics_handler: Option<DiagnosticsHandler>,
+}
+
+impl Localizer {
+    fn new() -> Self {
+        Self {
+            bundles: HashMap::new(),
+            fallback_locale: "en-US".parse().expect("Invalid fallback locale"),
+            reported: HashSet::new(),
+            diagnostics: Vec::new(),
+            diagnostics_handler: None,
+        }
+    }
+
+    /// Adds the messages in the `.ftl` file to the catalog of the locale.
+    pub fn add_file(
+        &mut self,
+        locale: &LanguageIdentifier,
+        path: impl AsRef<Path>,
+    ) -> Result<(), LocalizationError> {
+        let source =
+            fs::read_to_string(path).map_err(|error| LocalizationError::Io(error.to_string()))?;
+        self.add_source(locale, source)
+    }
+
+    /// Adds messages in the Fluent syntax to the catalog of the locale.
+    pub fn add_source(
+        &mut self,
+        locale: &LanguageIdentifier,
+        source: impl Into<String>,
+    ) -> Result<(), LocalizationError> {
+        let mut errors = Vec::new();
+        let resource =
+            FluentResource::try_new(source.into()).unwrap_or_else(|(resource, parse_errors)| {
+                errors.extend(parse_errors.iter().map(|error| error.to_string()));
+                resource
+            });
+
+        let bundle = self.bundles.entry(locale.clone()).or_insert_with(|| {
+            let mut bundle = FluentBundle::new_concurrent(vec![locale.clone()]);
+            // Isolation marks around arguments would show up as boxes in fonts that don't have them
+            bundle.set_use_isolating(false);
+            let number_locale = locale.clone();
+            bundle
+                .add_function("NUMBER", move |positional, named| {
+                    format::number_function(&number_locale, positional, named)
+                })
+                .expect("Adding NUMBER function failed");
+            let date_locale = locale.clone();
+            bundle
+                .add_function("DATE", move |positional, named| {
+                    format::date_function(&date_locale, positional, named)
+                })
+                .expect("Adding DATE function failed");
+            bundle
+        });
+        // Messages that are already in the catalog are kept, the rest of the resource is still added
+        if let Err(add_errors) = bundle.add_resource(resource) {
+            errors.extend(add_errors.iter().map(|error| error.to_string()));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(LocalizationError::Parse {
+                locale: locale.to_string(),
+                errors,
+            })
+        }
+    }
+
+    pub fn set_fallback_locale(&mut self, locale: LanguageIdentifier) {
+        self.fallback_locale = locale;
+    }
+
+    pub fn fallback_locale(&self) -> &LanguageIdentifier {
+        &self.fallback_locale
+    }
+
+    /// The locales that have a catalog.
+    pub fn locales(&self) -> Vec<LanguageIdentifier> {
+        self.bundles.keys().cloned().collect()
+    }
+
+    /// Called by `localize` for every problem the first time it occurs, while the localizer isn't locked.
+    pub fn set_diagnostics_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&LocalizationError) + Send + Sync + 'static,
+    {
+        self.diagnostics_handler = Some(Arc::new(handler));
+    }
+
+    /// The problems that were found since the last call.
+    pub fn take_diagnostics(&mut self) -> Vec<LocalizationError> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    // The catalog for the locale, falling back to a catalog for the same language with another region.
+    fn bundle(&self, locale: &LanguageIdentifier) -> Option<&FluentBundle<FluentResource>> {
+        self.bundles.get(locale).or_else(|| {
+            self.bundles
+                .iter()
+                .find(|(other, _)| other.language == locale.language)
+                .map(|(_, bundle)| bundle)
+        })
+    }
+
+    /// Formats the message with the arguments. `key` is a message id, or `id.attribute` for an attribute.
+    pub fn format(
+        &mut self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: &[(&str, &Var)],
+    ) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, fluent_value(value));
+        }
+
+        let (id, attribute) = match key.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (key, None),
+        };
+
+        for locale in [locale, &self.fallback_locale.clone()] {
+            let Some(bundle) = self.bundle(locale) else {
+                continue;
+            };
+
+            let pattern = bundle.get_message(id).and_then(|message| match attribute {
+                Some(attribute) => message
+                    .get_attribute(attribute)
+                    .map(|attribute| attribute.value()),
+                None => message.value(),
+            });
+
+            if let Some(pattern) = pattern {
+                let mut errors = Vec::new();
+                let text = bundle
+                    .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                    .to_string();
+                if !errors.is_empty() {
+                    self.report(LocalizationError::Format {
+                        locale: locale.to_string(),
+                        key: key.to_string(),
+                        errors: errors.iter().map(|error| error.to_string()).collect(),
+                    });
+                }
+
+                return text;
+            }
+        }
+
+        self.report(LocalizationError::MissingMessage {
+            locale: locale.to_string(),
+            key: key.to_string(),
+        });
+        key.to_string()
+    }
+
+    fn report(&mut self, error: LocalizationError) {
+        if self.reported.insert(error.clone()) {
+            self.diagnostics.push(error)
+        }
+    }
+}
+
+fn fluent_value(var: &Var) -> FluentValue<'static> {
+    match var {
+        Var::Real(value) => FluentValue::from(*value),
+        Var::Integer(value) => FluentValue::from(*value),
+        var => FluentValue::from(var.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LanguageIdentifier, LocalizationError, Localizer};
+    use crate::user_interface::value::Var;
+
+    fn locale(locale: &str) -> LanguageIdentifier {
+        locale.parse().unwrap()
+    }
+
+    fn localizer() -> Localizer {
+        let mut localizer = Localizer::new();
+        localizer
+            .add_source(
+                &locale("en-US"),
+                "hello = Hello\n\
+                 bye = Bye\n\
+                 items = { $count ->\n    [one] One item\n   *[other] { $count } items\n}\n",
+            )
+            .unwrap();
+        localizer
+            .add_source(&locale("de"), "hello = Hallo\n")
+            .unwrap();
+        localizer
+    }
+
+    #[test]
+    fn messages_fall_back_to_the_language_and_then_the_fallback_locale() {
+        let mut localizer = localizer();
+        assert_eq!(localizer.format(&locale("de"), "hello", &[]), "Hallo");
+        assert_eq!(localizer.format(&locale("de-AT"), "hello", &[]), "Hallo");
+        assert_eq!(localizer.format(&locale("de-AT"), "bye", &[]), "Bye");
+        assert_eq!(localizer.format(&locale("fr"), "hello", &[]), "Hello");
+        assert!(localizer.take_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn plural_variants_select_on_the_argument() {
+        let mut localizer = localizer();
+        let en = locale("en-US");
+        let one = Var::Integer(1);
+        let three = Var::Integer(3);
+        assert_eq!(
+            localizer.format(&en, "items", &[("count", &one)]),
+            "One item"
+        );
+        assert_eq!(
+            localizer.format(&en, "items", &[("count", &three)]),
+            "3 items"
+        );
+    }
+
+    #[test]
+    fn missing_messages_show_the_key_and_are_reported_once() {
+        let mut localizer = localizer();
+        let de = locale("de");
+        assert_eq!(localizer.format(&de, "missing", &[]), "missing");
+        assert_eq!(localizer.format(&de, "missing", &[]), "missing");
+        assert_eq!(
+            localizer.take_diagnostics(),
+            vec![LocalizationError::MissingMessage {
+                locale: "de".into(),
+                key: "missing".into(),
+            }]
+        );
+
+        localizer.format(&de, "missing", &[]);
+        assert!(localizer.take_diagnostics().is_empty());
+    }
+}
@@ -1,8 +1,8 @@
 use std::{any::Any, rc::Rc};
 
-use winit::window::WindowId;
+use winit::{event::ModifiersState, window::WindowId};
 
-use crate::geo::Point;
+use crate::geo::{Point, Transform};
 
 #[derive(PartialEq, Eq, Hash)]
 pub enum MouseEventType {
@@ -21,6 +21,12 @@ pub enum MouseButton {
     Other(u8),
 }
 
+const CONTROL: u32 = 1;
+const SHIFT: u32 = 2;
+const RIGHT_MOUSE: u32 = 4;
+const ALT: u32 = 8;
+const LOGO: u32 = 16;
+
 #[derive(Clone)]
 pub struct MouseEventData {
     window_id: WindowId,
@@ -31,6 +37,7 @@ pub struct MouseEventData {
     drag_start: Option<Point>,
     drag_data: Option<Rc<dyn Any>>,
     scroll: Option<(f32, f32)>,
+    click_count: usize,
 }
 
 impl MouseEventData {
@@ -49,12 +56,14 @@ impl MouseEventData {
             drag_start: None,
             drag_data: None,
             scroll: None,
+            click_count: 1,
         }
     }
 
-    pub fn to_local(&self, position: &Point) -> Self {
+    /// Maps the position with the transform from window to local coordinates.
+    pub fn to_local(&self, to_local: &Transform) -> Self {
         let mut new = self.clone();
-        new.local_position = self.local_position - *position;
+        new.local_position = to_local.map_point(self.local_position);
         new
     }
 
@@ -74,6 +83,7 @@ impl MouseEventData {
             drag_start: None,
             drag_data: None,
             scroll: None,
+            click_count: 1,
         }
     }
 
@@ -82,6 +92,11 @@ impl MouseEventData {
         self
     }
 
+    pub fn with_click_count(mut self, click_count: usize) -> Self {
+        self.click_count = click_count;
+        self
+    }
+
     pub fn with_delta(mut self, delta: Point) -> Self {
         self.delta_position = delta;
         self
@@ -102,20 +117,50 @@ impl MouseEventData {
         Point::new(self.scroll.unwrap().0, self.scroll.unwrap().1)
     }
 
+    /// How many times the button was pressed in quick succession at the same place, 2 for a double click.
+    pub fn click_count(&self) -> usize {
+        self.click_count
+    }
+
     pub fn drag_start(&self) -> &Option<Point> {
         &self.drag_start
     }
 
+    /// Takes the modifier keys from the keys that are held on the keyboard.
+    pub fn with_modifiers(mut self, modifiers: ModifiersState) -> Self {
+        self.modifiers &= RIGHT_MOUSE;
+        for (held, flag) in [
+            (modifiers.ctrl(), CONTROL),
+            (modifiers.shift(), SHIFT),
+            (modifiers.alt(), ALT),
+            (modifiers.logo(), LOGO),
+        ] {
+            if held {
+                self.modifiers |= flag;
+            }
+        }
+        self
+    }
+
     pub fn is_control_down(&self) -> bool {
-        (self.modifiers & 1) != 0
+        (self.modifiers & CONTROL) != 0
     }
 
     pub fn is_shift_down(&self) -> bool {
-        (self.modifiers & 2) != 0
+        (self.modifiers & SHIFT) != 0
+    }
+
+    pub fn is_alt_down(&self) -> bool {
+        (self.modifiers & ALT) != 0
+    }
+
+    /// The command key on macOS, the Windows key elsewhere.
+    pub fn is_logo_down(&self) -> bool {
+        (self.modifiers & LOGO) != 0
     }
 
     pub fn is_right_mouse(&self) -> bool {
-        (self.modifiers & 4) != 0
+        (self.modifiers & RIGHT_MOUSE) != 0
     }
 
     pub fn global_position(&self) -> &Point {
@@ -17,4 +17,7 @@ pub trait Painter: Send {
     fn mounted(&self, _render_ctx: &mut RenderCtx) {}
     fn animation_event(&mut self, _ctx: &mut AnimationCtx) {}
     fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas);
+    /// Called after the children are painted, before the canvas is restored. A painter that opened a
+    /// layer in `paint` closes it here, so the children are painted into the layer.
+    fn paint_after_children(&self, _paint_ctx: &PaintCtx, _canvas: &mut dyn Canvas) {}
 }
@@ -1,10 +1,20 @@
-use crate::geo::Rect;
+use crate::{
+    canvas::{
+        font::Font,
+        text_measurer::{ShapingTextMeasurer, TextMeasurer, TextMetrics},
+    },
+    geo::Rect,
+    widget::style::TextDirection,
+};
 use std::any::Any;
 
 pub struct PaintCtx<'a> {
     global_bounds: &'a Rect,
     local_bounds: &'a Rect,
     state: Option<&'a (dyn Any + Send)>,
+    text_direction: TextDirection,
+    text_measurer: &'a dyn TextMeasurer,
+    dpi: f32,
 }
 
 impl<'a> PaintCtx<'a> {
@@ -17,9 +27,49 @@ impl<'a> PaintCtx<'a> {
             global_bounds,
             local_bounds,
             state,
+            text_direction: TextDirection::default(),
+            text_measurer: &ShapingTextMeasurer,
+            dpi: 1.0,
         }
     }
 
+    /// The measurer painters size their text with, text is shaped without a cache when none is given.
+    pub fn with_text_measurer(mut self, text_measurer: &'a dyn TextMeasurer) -> Self {
+        self.text_measurer = text_measurer;
+        self
+    }
+
+    pub fn text_measurer(&self) -> &'a dyn TextMeasurer {
+        self.text_measurer
+    }
+
+    /// Measures the text as a single line, shaped with the direction the painter inherits.
+    pub fn measure_text(&self, text: &str, font: &Font) -> TextMetrics {
+        self.text_measurer
+            .measure(text, font, self.text_direction())
+    }
+
+    pub fn with_dpi(mut self, dpi: f32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// The number of pixels per unit of the bounds, painters use it to prepare images at the size
+    /// they end up on the screen.
+    pub fn dpi(&self) -> f32 {
+        self.dpi
+    }
+
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
+    /// Painters mirror things like arrow icons for right to left text.
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction
+    }
+
     pub fn global_bounds(&self) -> &'a Rect {
         self.global_bounds
     }
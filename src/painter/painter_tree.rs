@@ -1,4 +1,4 @@
-use crate::{geo::Rect, painter::Painter, tree::Tree};
+use crate::{geo::Rect, painter::Painter, tree::Tree, widget::style::TextDirection};
 use std::{any::Any, sync::Arc};
 
 pub type PainterTree = Tree<PainterElement>;
@@ -8,6 +8,7 @@ pub struct PainterElement {
     painter_state: Option<Arc<dyn Any + Send>>,
     pub local_bounds: Rect,
     pub global_bounds: Rect,
+    pub text_direction: TextDirection,
 }
 unsafe impl Send for PainterElement {}
 impl PainterElement {
@@ -20,6 +21,7 @@ impl PainterElement {
             painter_state,
             local_bounds: Rect::default(),
             global_bounds: Rect::default(),
+            text_direction: TextDirection::default(),
         }
     }
 
@@ -29,6 +31,11 @@ impl PainterElement {
         self
     }
 
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
     pub fn painter_state(&self) -> Option<&(dyn Any + Send)> {
         self.painter_state.as_deref()
     }
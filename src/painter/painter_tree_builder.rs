@@ -16,7 +16,9 @@ impl PainterTreeBuilder {
         for (id, node) in widget_tree.nodes() {
             let painter = node.data.widget().painter(ui_state);
             let state = node.data.state();
-            painter_tree.add_node_with_id(*id, Node::new(PainterElement::new(painter, state)));
+            let element =
+                PainterElement::new(painter, state).with_text_direction(node.data.text_direction());
+            painter_tree.add_node_with_id(*id, Node::new(element));
             for child in &node.children {
                 painter_tree.add_child(*id, *child);
             }
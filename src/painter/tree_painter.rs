@@ -4,7 +4,7 @@ use crate::{
         animation_ctx::AnimationCtx, animation_event::AnimationEvent,
         animation_request::AnimationRequest,
     },
-    canvas::Canvas,
+    canvas::{text_measurer::SkiaTextMeasurer, Canvas},
     geo::{Point, Rect, Size},
     tree::ElementId,
 };
@@ -23,6 +23,7 @@ pub struct TreePainter {
     rx: Receiver<TreePainterMessage>,
     size: Size,
     dpi: f32,
+    text_measurer: SkiaTextMeasurer,
 }
 
 impl TreePainter {
@@ -34,6 +35,7 @@ impl TreePainter {
             drag_tree: None,
             rx,
             dpi,
+            text_measurer: SkiaTextMeasurer::new(),
         };
         (tree_painter, tx)
     }
@@ -91,7 +93,8 @@ impl TreePainter {
         parent: usize,
         tree: PainterTree,
     ) -> HashMap<ElementId, AnimationRequest> {
-        self.tree.remove_node(tree.root_id());
+        // The parent keeps the rebuilt node in its place among its children
+        self.tree.take_subtree(tree.root_id());
         let new_nodes = self.tree.merge_subtree(parent, tree);
         let mut animation_requests = HashMap::new();
         for id in new_nodes {
@@ -126,19 +129,33 @@ impl TreePainter {
 
             if let Some(painter) = node.data.painter() {
                 let paint_ctx =
-                    PaintCtx::new(&global_bounds, &local_bounds, node.data.painter_state());
+                    PaintCtx::new(&global_bounds, &local_bounds, node.data.painter_state())
+                        .with_text_direction(node.data.text_direction)
+                        .with_text_measurer(&self.text_measurer)
+                        .with_dpi(self.dpi);
                 painter.paint(&paint_ctx, canvas);
             }
 
-            Some(node.children.clone())
+            Some((node.children.clone(), global_bounds, local_bounds))
         } else {
             None
         };
 
-        if let Some(children) = children {
+        if let Some((children, global_bounds, local_bounds)) = children {
             for child in children {
                 self.paint_drag_source_element(child, offset, canvas);
             }
+
+            if let Some(node) = self.drag_tree.as_ref().unwrap().get(id) {
+                if let Some(painter) = node.data.painter() {
+                    let paint_ctx =
+                        PaintCtx::new(&global_bounds, &local_bounds, node.data.painter_state())
+                            .with_text_direction(node.data.text_direction)
+                            .with_text_measurer(&self.text_measurer)
+                            .with_dpi(self.dpi);
+                    painter.paint_after_children(&paint_ctx, canvas);
+                }
+            }
         }
 
         canvas.restore()
@@ -169,19 +186,33 @@ impl TreePainter {
 
             if let Some(painter) = node.data.painter() {
                 let paint_ctx =
-                    PaintCtx::new(&global_bounds, &local_bounds, node.data.painter_state());
+                    PaintCtx::new(&global_bounds, &local_bounds, node.data.painter_state())
+                        .with_text_direction(node.data.text_direction)
+                        .with_text_measurer(&self.text_measurer)
+                        .with_dpi(self.dpi);
                 painter.paint(&paint_ctx, canvas);
             }
 
-            Some(node.children.clone())
+            Some((node.children.clone(), global_bounds, local_bounds))
         } else {
             None
         };
 
-        if let Some(children) = children {
+        if let Some((children, global_bounds, local_bounds)) = children {
             for child in children {
                 self.paint_element(child, offset, canvas);
             }
+
+            if let Some(node) = self.tree.get(id) {
+                if let Some(painter) = node.data.painter() {
+                    let paint_ctx =
+                        PaintCtx::new(&global_bounds, &local_bounds, node.data.painter_state())
+                            .with_text_direction(node.data.text_direction)
+                            .with_text_measurer(&self.text_measurer)
+                            .with_dpi(self.dpi);
+                    painter.paint_after_children(&paint_ctx, canvas);
+                }
+            }
         }
 
         canvas.restore()
@@ -0,0 +1,78 @@
+use std::rc::Rc;
+
+use crate::{
+    geo::{Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{
+        constraints::BoxConstraints, style::Alignment, BuildCtx, Child, Children, LayoutCtx,
+        SizeCtx, Widget,
+    },
+};
+
+/// Takes all the space it is given and positions its child inside it.
+/// The alignment is either fractional or given as a pair of horizontal and vertical alignments.
+/// Start and end alignments follow the text direction.
+pub struct Align {
+    child: Child,
+    alignment: Alignment,
+}
+
+impl Align {
+    pub fn new<C>(alignment: impl Into<Alignment>, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            alignment: alignment.into(),
+        }
+    }
+}
+
+impl Widget for Align {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        // Without bounds on an axis we shrink to the child on that axis.
+        let child_size = size_ctx.preferred_size(children[0], &constraints.loosened());
+        let width = constraints
+            .max_width()
+            .or(child_size.map(|size| size.width));
+        let height = constraints
+            .max_height()
+            .or(child_size.map(|size| size.height));
+
+        match (width, height) {
+            (Some(width), Some(height)) => Some(Size::new(width, height)),
+            _ => None,
+        }
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        let child_size = layout_ctx
+            .preferred_size(
+                children[0],
+                &BoxConstraints::new_with_max(size.width, size.height),
+            )
+            .unwrap_or(size);
+
+        let alignment = self.alignment.resolve(layout_ctx.text_direction());
+        layout_ctx.set_child_bounds(
+            children[0],
+            Rect::new(alignment.position(child_size, size), child_size),
+        )
+    }
+}
@@ -0,0 +1,74 @@
+use std::rc::Rc;
+
+use crate::{
+    geo::{Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{constraints::BoxConstraints, BuildCtx, Child, Children, LayoutCtx, SizeCtx, Widget},
+};
+
+/// Sizes its child to a fixed width to height ratio.
+/// It takes as much width as it is allowed and derives the height from it,
+/// unless that height does not fit, in which case the width is derived from the maximum height.
+/// A ratio that isn't a positive number doesn't constrain the child, it takes its preferred size.
+pub struct AspectRatio {
+    child: Child,
+    ratio: Option<f32>,
+}
+
+impl AspectRatio {
+    pub fn new<C>(ratio: f32, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            ratio: (ratio.is_finite() && ratio > 0.0).then_some(ratio),
+        }
+    }
+}
+
+impl Widget for AspectRatio {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        let Some(ratio) = self.ratio else {
+            return size_ctx.preferred_size(children[0], constraints);
+        };
+
+        let size = match (constraints.max_width(), constraints.max_height()) {
+            (Some(width), Some(height)) => {
+                if width / ratio <= height {
+                    Size::new(width, width / ratio)
+                } else {
+                    Size::new(height * ratio, height)
+                }
+            }
+            (Some(width), None) => Size::new(width, width / ratio),
+            (None, Some(height)) => Size::new(height * ratio, height),
+            (None, None) => {
+                // Unbounded in both directions, let the child's width decide.
+                let child_size = size_ctx.preferred_size(children[0], constraints)?;
+                Size::new(child_size.width, child_size.width / ratio)
+            }
+        };
+
+        Some(constraints.constrain(size))
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        layout_ctx.set_child_bounds(children[0], Rect::new_from_size(size))
+    }
+}
@@ -0,0 +1,227 @@
+use std::{rc::Rc, sync::Arc};
+
+use crate::{
+    canvas::{path::Path, Canvas},
+    geo::{Point, Rect, Size},
+    painter::{PaintCtx, Painter},
+    user_interface::ui_state::UIState,
+    widget::{constraints::BoxConstraints, BuildCtx, Child, Children, LayoutCtx, SizeCtx, Widget},
+};
+
+/// Creates the clip shape for the size of the widget, in its local coordinates.
+/// It is shared with the painter on the render thread.
+pub type ClipShape = Arc<dyn Fn(Size) -> Path + Send + Sync>;
+
+fn clip_size(children: &[usize], constraints: &BoxConstraints, size_ctx: &SizeCtx) -> Option<Size> {
+    size_ctx.preferred_size(children[0], constraints)
+}
+
+fn clip_layout(layout_ctx: &mut LayoutCtx, size: Size, children: &[usize]) {
+    layout_ctx.set_child_bounds(children[0], Rect::new_from_size(size))
+}
+
+/// Clips its child to its bounds, the parts of the child outside of them are neither painted nor hit.
+pub struct ClipRect {
+    child: Child,
+}
+
+impl ClipRect {
+    pub fn new<C>(child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+        }
+    }
+}
+
+impl Widget for ClipRect {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        clip_size(children, constraints, size_ctx)
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        clip_layout(layout_ctx, size, children)
+    }
+
+    fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(ClipRectPainter {}))
+    }
+}
+
+pub struct ClipRectPainter {}
+
+impl Painter for ClipRectPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        canvas.clip_rect(&Rect::new_from_size(paint_ctx.local_bounds().size()))
+    }
+}
+
+/// Clips its child to its bounds with rounded corners, for cards and rounded avatars.
+/// A radius of half the size clips to a circle or a capsule.
+pub struct ClipRRect {
+    child: Child,
+    radius: f32,
+}
+
+impl ClipRRect {
+    pub fn new<C>(radius: f32, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            radius,
+        }
+    }
+}
+
+// Rounded corners can't be rounder than half of the shorter side.
+fn corner_radius(radius: f32, size: Size) -> f32 {
+    radius.min(size.width / 2.0).min(size.height / 2.0).max(0.0)
+}
+
+impl Widget for ClipRRect {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        clip_size(children, constraints, size_ctx)
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        clip_layout(layout_ctx, size, children)
+    }
+
+    fn hit_test(&self, position: &Point, size: Size) -> bool {
+        let radius = corner_radius(self.radius, size);
+        let mut path = Path::new();
+        path.add_rounded_rect(Rect::new_from_size(size), radius, radius);
+        path.contains(position)
+    }
+
+    fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(ClipRRectPainter {
+            radius: self.radius,
+        }))
+    }
+}
+
+pub struct ClipRRectPainter {
+    radius: f32,
+}
+
+impl Painter for ClipRRectPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        let size = paint_ctx.local_bounds().size();
+        let radius = corner_radius(self.radius, size);
+        canvas.clip_rounded_rect(&Rect::new_from_size(size), radius, radius)
+    }
+}
+
+/// Clips its child to a path that is created for the size of the widget.
+pub struct ClipPath {
+    child: Child,
+    shape: ClipShape,
+}
+
+impl ClipPath {
+    pub fn new<S, C>(shape: S, child: C) -> Self
+    where
+        S: Fn(Size) -> Path + Send + Sync + 'static,
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            shape: Arc::new(shape),
+        }
+    }
+
+    /// Clips to the oval that fills the bounds.
+    pub fn oval<C>(child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self::new(
+            |size| {
+                let mut path = Path::new();
+                path.add_oval(Rect::new_from_size(size));
+                path
+            },
+            child,
+        )
+    }
+}
+
+impl Widget for ClipPath {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        clip_size(children, constraints, size_ctx)
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        clip_layout(layout_ctx, size, children)
+    }
+
+    fn hit_test(&self, position: &Point, size: Size) -> bool {
+        (self.shape)(size).contains(position)
+    }
+
+    fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(ClipPathPainter {
+            shape: self.shape.clone(),
+        }))
+    }
+}
+
+pub struct ClipPathPainter {
+    shape: ClipShape,
+}
+
+impl Painter for ClipPathPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        canvas.clip_path(&(self.shape)(paint_ctx.local_bounds().size()))
+    }
+}
@@ -0,0 +1,58 @@
+use std::rc::Rc;
+
+use crate::{
+    geo::{Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{constraints::BoxConstraints, BuildCtx, Child, Children, LayoutCtx, SizeCtx, Widget},
+};
+
+/// Imposes additional constraints on its child, on top of the constraints given by its parent.
+/// The constraints of the parent always win.
+pub struct ConstrainedBox {
+    child: Child,
+    constraints: BoxConstraints,
+}
+
+impl ConstrainedBox {
+    pub fn new<C>(constraints: BoxConstraints, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            constraints,
+        }
+    }
+}
+
+impl Widget for ConstrainedBox {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        let constraints = self.constraints.enforce(constraints);
+        if let Some(child_size) = size_ctx.preferred_size(children[0], &constraints) {
+            Some(constraints.constrain(child_size))
+        } else if constraints.has_max() {
+            Some(constraints.max_size())
+        } else {
+            None
+        }
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        layout_ctx.set_child_bounds(children[0], Rect::new_from_size(size))
+    }
+}
@@ -0,0 +1,661 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Display,
+    ops::{Add, Mul, Sub},
+    rc::Rc,
+};
+
+use cassowary::{
+    strength::{MEDIUM, REQUIRED, STRONG, WEAK},
+    Constraint, Expression, RelationalOperator, Solver, Term, Variable,
+};
+
+use crate::{
+    geo::{Point, Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{constraints::BoxConstraints, BuildCtx, Child, Children, LayoutCtx, SizeCtx, Widget},
+};
+
+/// Name that refers to the `ConstraintLayout` itself in constraints.
+pub const PARENT: &str = "parent";
+
+/// A problem with the children or the constraints of a `ConstraintLayout`. The layout doesn't fail,
+/// children with an invalid name get empty bounds and invalid constraints are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintLayoutError {
+    /// A child uses `PARENT` as its name.
+    ReservedName,
+    /// Another child already has the name, only the first one is laid out.
+    DuplicateName(String),
+    /// The constraint at the index refers to a child that doesn't exist.
+    UnknownName { constraint: usize, name: String },
+    /// The required constraint at the index conflicts with the constraints before it.
+    Unsatisfiable { constraint: usize },
+}
+
+impl Display for ConstraintLayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstraintLayoutError::ReservedName => {
+                write!(f, "'{}' is reserved for the layout itself", PARENT)
+            }
+            ConstraintLayoutError::DuplicateName(name) => {
+                write!(f, "More than one child is named '{}'", name)
+            }
+            ConstraintLayoutError::UnknownName { constraint, name } => write!(
+                f,
+                "Constraint {} refers to unknown child '{}'",
+                constraint, name
+            ),
+            ConstraintLayoutError::Unsatisfiable { constraint } => {
+                write!(f, "Constraint {} can't be satisfied", constraint)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConstraintLayoutError {}
+
+type DiagnosticsHandler = Rc<dyn Fn(&ConstraintLayoutError)>;
+
+#[derive(Clone, Copy)]
+pub enum Anchor {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    Width,
+    Height,
+}
+
+#[derive(Clone, Copy)]
+pub enum Strength {
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+
+impl Strength {
+    fn value(&self) -> f64 {
+        match self {
+            Strength::Required => REQUIRED,
+            Strength::Strong => STRONG,
+            Strength::Medium => MEDIUM,
+            Strength::Weak => WEAK,
+        }
+    }
+}
+
+/// A linear combination of anchors plus a constant, for example `a.right + 8`.
+#[derive(Clone)]
+pub struct AnchorExpression {
+    terms: Vec<(String, Anchor, f32)>,
+    constant: f32,
+}
+
+/// Refers to an anchor of the child with the given name, or of the layout itself when the name is `PARENT`.
+pub fn anchor(name: &str, anchor: Anchor) -> AnchorExpression {
+    AnchorExpression {
+        terms: vec![(name.to_string(), anchor, 1.0)],
+        constant: 0.0,
+    }
+}
+
+impl AnchorExpression {
+    pub fn equals(self, rhs: impl Into<AnchorExpression>) -> LayoutConstraint {
+        LayoutConstraint::new(self, RelationalOperator::Equal, rhs.into())
+    }
+
+    pub fn at_most(self, rhs: impl Into<AnchorExpression>) -> LayoutConstraint {
+        LayoutConstraint::new(self, RelationalOperator::LessOrEqual, rhs.into())
+    }
+
+    pub fn at_least(self, rhs: impl Into<AnchorExpression>) -> LayoutConstraint {
+        LayoutConstraint::new(self, RelationalOperator::GreaterOrEqual, rhs.into())
+    }
+}
+
+impl From<f32> for AnchorExpression {
+    fn from(constant: f32) -> Self {
+        Self {
+            terms: Vec::new(),
+            constant,
+        }
+    }
+}
+
+impl Add<f32> for AnchorExpression {
+    type Output = Self;
+
+    fn add(mut self, rhs: f32) -> Self::Output {
+        self.constant += rhs;
+        self
+    }
+}
+
+impl Sub<f32> for AnchorExpression {
+    type Output = Self;
+
+    fn sub(mut self, rhs: f32) -> Self::Output {
+        self.constant -= rhs;
+        self
+    }
+}
+
+impl Mul<f32> for AnchorExpression {
+    type Output = Self;
+
+    fn mul(mut self, rhs: f32) -> Self::Output {
+        for term in &mut self.terms {
+            term.2 *= rhs;
+        }
+        self.constant *= rhs;
+        self
+    }
+}
+
+impl Add<AnchorExpression> for AnchorExpression {
+    type Output = Self;
+
+    fn add(mut self, rhs: AnchorExpression) -> Self::Output {
+        self.terms.extend(rhs.terms);
+        self.constant += rhs.constant;
+        self
+    }
+}
+
+impl Sub<AnchorExpression> for AnchorExpression {
+    type Output = Self;
+
+    fn sub(self, rhs: AnchorExpression) -> Self::Output {
+        self + rhs * -1.0
+    }
+}
+
+/// A relation between two anchor expressions, with a priority.
+/// Constraints are `Required` unless given another strength.
+#[derive(Clone)]
+pub struct LayoutConstraint {
+    lhs: AnchorExpression,
+    relation: RelationalOperator,
+    rhs: AnchorExpression,
+    strength: Strength,
+}
+
+impl LayoutConstraint {
+    fn new(lhs: AnchorExpression, relation: RelationalOperator, rhs: AnchorExpression) -> Self {
+        Self {
+            lhs,
+            relation,
+            rhs,
+            strength: Strength::Required,
+        }
+    }
+
+    pub fn with_strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+#[derive(Clone, Copy)]
+struct AnchorVariables {
+    left: Variable,
+    top: Variable,
+    width: Variable,
+    height: Variable,
+}
+
+impl AnchorVariables {
+    fn new() -> Self {
+        Self {
+            left: Variable::new(),
+            top: Variable::new(),
+            width: Variable::new(),
+            height: Variable::new(),
+        }
+    }
+
+    fn terms(&self, anchor: Anchor, coefficient: f64) -> Vec<Term> {
+        let term = |variable, factor: f64| Term {
+            variable,
+            coefficient: coefficient * factor,
+        };
+
+        match anchor {
+            Anchor::Left => vec![term(self.left, 1.0)],
+            Anchor::Right => vec![term(self.left, 1.0), term(self.width, 1.0)],
+            Anchor::Top => vec![term(self.top, 1.0)],
+            Anchor::Bottom => vec![term(self.top, 1.0), term(self.height, 1.0)],
+            Anchor::CenterX => vec![term(self.left, 1.0), term(self.width, 0.5)],
+            Anchor::CenterY => vec![term(self.top, 1.0), term(self.height, 0.5)],
+            Anchor::Width => vec![term(self.width, 1.0)],
+            Anchor::Height => vec![term(self.height, 1.0)],
+        }
+    }
+}
+
+// The size of the layout itself has to win from everything but required constraints.
+const PARENT_SIZE_STRENGTH: f64 = STRONG * 100.0;
+
+/// Incremental solver for the constraints of a `ConstraintLayout`.
+/// It is created once, after which only the edit variables for the sizes are updated on every layout.
+struct LayoutSolver {
+    solver: Solver,
+    parent: AnchorVariables,
+    children: HashMap<String, AnchorVariables>,
+}
+
+impl LayoutSolver {
+    fn new<'a>(
+        names: impl Iterator<Item = &'a String>,
+        constraints: &[LayoutConstraint],
+        report: &dyn Fn(ConstraintLayoutError),
+    ) -> Self {
+        let mut solver = Solver::new();
+        let parent = AnchorVariables::new();
+        let mut children = HashMap::new();
+
+        let mut required = vec![
+            Constraint::new(
+                Expression::from_term(Term {
+                    variable: parent.left,
+                    coefficient: 1.0,
+                }),
+                RelationalOperator::Equal,
+                REQUIRED,
+            ),
+            Constraint::new(
+                Expression::from_term(Term {
+                    variable: parent.top,
+                    coefficient: 1.0,
+                }),
+                RelationalOperator::Equal,
+                REQUIRED,
+            ),
+        ];
+
+        for name in names {
+            if name == PARENT {
+                report(ConstraintLayoutError::ReservedName);
+                continue;
+            }
+            if children.contains_key(name) {
+                report(ConstraintLayoutError::DuplicateName(name.clone()));
+                continue;
+            }
+
+            let variables = AnchorVariables::new();
+            for variable in [variables.width, variables.height] {
+                required.push(Constraint::new(
+                    Expression::from_term(Term {
+                        variable,
+                        coefficient: 1.0,
+                    }),
+                    RelationalOperator::GreaterOrEqual,
+                    REQUIRED,
+                ));
+                // The preferred size of a child is a weak suggestion
+                solver
+                    .add_edit_variable(variable, WEAK)
+                    .expect("Adding child size variable failed");
+            }
+
+            children.insert(name.clone(), variables);
+        }
+
+        solver
+            .add_constraints(&required)
+            .expect("Adding layout constraints failed");
+        solver
+            .add_edit_variable(parent.width, PARENT_SIZE_STRENGTH)
+            .expect("Adding parent width variable failed");
+        solver
+            .add_edit_variable(parent.height, PARENT_SIZE_STRENGTH)
+            .expect("Adding parent height variable failed");
+
+        let mut layout_solver = Self {
+            solver,
+            parent,
+            children,
+        };
+
+        for (index, constraint) in constraints.iter().enumerate() {
+            let constraint = match layout_solver.constraint(constraint) {
+                Ok(constraint) => constraint,
+                Err(name) => {
+                    report(ConstraintLayoutError::UnknownName {
+                        constraint: index,
+                        name,
+                    });
+                    continue;
+                }
+            };
+            // A constraint that fails isn't added, the solver stays as it was before
+            if layout_solver.solver.add_constraint(constraint).is_err() {
+                report(ConstraintLayoutError::Unsatisfiable { constraint: index });
+            }
+        }
+
+        layout_solver
+    }
+
+    fn variables(&self, name: &str) -> Option<&AnchorVariables> {
+        if name == PARENT {
+            Some(&self.parent)
+        } else {
+            self.children.get(name)
+        }
+    }
+
+    // The name of the first unknown child is the error.
+    fn expression(&self, expression: &AnchorExpression, sign: f64) -> Result<Expression, String> {
+        let mut terms = Vec::new();
+        for (name, anchor, coefficient) in &expression.terms {
+            let variables = self.variables(name).ok_or_else(|| name.clone())?;
+            terms.extend(variables.terms(*anchor, *coefficient as f64 * sign));
+        }
+
+        Ok(Expression::new(terms, expression.constant as f64 * sign))
+    }
+
+    fn constraint(&self, constraint: &LayoutConstraint) -> Result<Constraint, String> {
+        // lhs op rhs is expressed as lhs - rhs op 0
+        let mut expression = self.expression(&constraint.lhs, 1.0)?;
+        let rhs = self.expression(&constraint.rhs, -1.0)?;
+        expression.terms.extend(rhs.terms);
+        expression.constant += rhs.constant;
+
+        Ok(Constraint::new(
+            expression,
+            constraint.relation,
+            constraint.strength.value(),
+        ))
+    }
+
+    fn suggest(&mut self, variable: Variable, value: f32) {
+        self.solver
+            .suggest_value(variable, value as f64)
+            .expect("Suggesting layout value failed");
+    }
+
+    fn bounds(&self, name: &str) -> Option<Rect> {
+        let variables = self.variables(name)?;
+        let value = |variable| self.solver.get_value(variable) as f32;
+        Some(Rect::new(
+            Point::new(value(variables.left), value(variables.top)),
+            Size::new(value(variables.width), value(variables.height)),
+        ))
+    }
+}
+
+/// Lays out named children according to linear constraints between their anchors, for example
+/// `(anchor("a", Anchor::Right) + 8.0).equals(anchor("b", Anchor::Left))`.
+/// The solver is kept between layouts and re-runs whenever the layout is resized.
+/// The preferred size of a child is used as a weak suggestion for its width and height.
+/// Invalid names and constraints that can't be satisfied are reported to the diagnostics handler
+/// when the solver is created.
+pub struct ConstraintLayout {
+    children: Vec<(String, Child)>,
+    constraints: Vec<LayoutConstraint>,
+    diagnostics_handler: Option<DiagnosticsHandler>,
+    solver: RefCell<Option<LayoutSolver>>,
+}
+
+impl ConstraintLayout {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            constraints: Vec::new(),
+            diagnostics_handler: None,
+            solver: RefCell::new(None),
+        }
+    }
+
+    pub fn with_child<C>(mut self, name: &str, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        self.children.push((name.to_string(), Rc::new(child)));
+        self
+    }
+
+    pub fn with_constraint(mut self, constraint: LayoutConstraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Called for every problem with the children or the constraints, without a handler they
+    /// are only left out of the layout.
+    pub fn with_diagnostics_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ConstraintLayoutError) + 'static,
+    {
+        self.diagnostics_handler = Some(Rc::new(handler));
+        self
+    }
+
+    fn report(&self, error: ConstraintLayoutError) {
+        if let Some(handler) = &self.diagnostics_handler {
+            (handler)(&error);
+        }
+    }
+}
+
+impl Default for ConstraintLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for ConstraintLayout {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        self.children
+            .iter()
+            .map(|(_, child)| child(build_ctx.ui_state()))
+            .collect()
+    }
+
+    fn calculate_size(
+        &self,
+        _children: &[usize],
+        constraints: &BoxConstraints,
+        _size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        Some(constraints.max_size())
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        let mut solver = self.solver.borrow_mut();
+        let solver = solver.get_or_insert_with(|| {
+            LayoutSolver::new(
+                self.children.iter().map(|(name, _)| name),
+                &self.constraints,
+                &|error| self.report(error),
+            )
+        });
+
+        let parent = solver.parent;
+        solver.suggest(parent.width, size.width);
+        solver.suggest(parent.height, size.height);
+
+        // Only the first child with a name gets its size suggested and its bounds, the others
+        // are left out like the solver leaves them out
+        let child_constraints = BoxConstraints::new_with_max(size.width, size.height);
+        let mut suggested = Vec::new();
+        for ((name, _), id) in self.children.iter().zip(children) {
+            if name == PARENT || suggested.contains(&name) {
+                continue;
+            }
+            suggested.push(name);
+            let Some(variables) = solver.variables(name).copied() else {
+                continue;
+            };
+            if let Some(preferred) = layout_ctx.preferred_size(*id, &child_constraints) {
+                solver.suggest(variables.width, preferred.width);
+                solver.suggest(variables.height, preferred.height);
+            }
+        }
+
+        let mut laid_out = Vec::new();
+        for ((name, _), id) in self.children.iter().zip(children) {
+            let bounds = match solver.bounds(name) {
+                Some(bounds) if name != PARENT && !laid_out.contains(&name) => bounds,
+                _ => Rect::new_from_size(Size::new(0.0, 0.0)),
+            };
+            laid_out.push(name);
+            layout_ctx.set_child_bounds(*id, bounds);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::{
+        anchor, Anchor, ConstraintLayoutError, LayoutConstraint, LayoutSolver, Strength, PARENT,
+    };
+    use crate::geo::{Point, Rect, Size};
+
+    // Solves the constraints for the parent size, with the preferred sizes of the children.
+    fn solve(
+        names: &[&str],
+        constraints: &[LayoutConstraint],
+        size: Size,
+        preferred: &[(&str, Size)],
+    ) -> (LayoutSolver, Vec<ConstraintLayoutError>) {
+        let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        let errors = RefCell::new(Vec::new());
+        let mut solver = LayoutSolver::new(names.iter(), constraints, &|error| {
+            errors.borrow_mut().push(error)
+        });
+
+        let parent = solver.parent;
+        solver.suggest(parent.width, size.width);
+        solver.suggest(parent.height, size.height);
+        for (name, size) in preferred {
+            let variables = *solver.variables(name).unwrap();
+            solver.suggest(variables.width, size.width);
+            solver.suggest(variables.height, size.height);
+        }
+
+        (solver, errors.into_inner())
+    }
+
+    fn assert_bounds(solver: &LayoutSolver, name: &str, expected: Rect) {
+        let bounds = solver.bounds(name).unwrap();
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        assert!(
+            close(bounds.left(), expected.left())
+                && close(bounds.top(), expected.top())
+                && close(bounds.size().width, expected.size().width)
+                && close(bounds.size().height, expected.size().height),
+            "{:?} != {:?}",
+            bounds,
+            expected
+        );
+    }
+
+    #[test]
+    fn anchors_place_children() {
+        let constraints = [
+            anchor("a", Anchor::Left).equals(anchor(PARENT, Anchor::Left) + 10.0),
+            anchor("a", Anchor::Top).equals(anchor(PARENT, Anchor::Top) + 5.0),
+            anchor("b", Anchor::Left).equals(anchor("a", Anchor::Right) + 8.0),
+            anchor("b", Anchor::CenterY).equals(anchor(PARENT, Anchor::CenterY)),
+        ];
+        let (solver, errors) = solve(
+            &["a", "b"],
+            &constraints,
+            Size::new(200.0, 100.0),
+            &[("a", Size::new(20.0, 10.0)), ("b", Size::new(30.0, 20.0))],
+        );
+
+        assert!(errors.is_empty());
+        assert_bounds(
+            &solver,
+            "a",
+            Rect::new(Point::new(10.0, 5.0), Size::new(20.0, 10.0)),
+        );
+        assert_bounds(
+            &solver,
+            "b",
+            Rect::new(Point::new(38.0, 40.0), Size::new(30.0, 20.0)),
+        );
+    }
+
+    #[test]
+    fn stronger_constraints_win() {
+        let constraints = [
+            anchor("a", Anchor::Width)
+                .equals(50.0)
+                .with_strength(Strength::Weak),
+            anchor("a", Anchor::Width)
+                .equals(100.0)
+                .with_strength(Strength::Strong),
+            anchor("a", Anchor::Height)
+                .equals(anchor(PARENT, Anchor::Height))
+                .with_strength(Strength::Medium),
+        ];
+        let (solver, errors) = solve(
+            &["a"],
+            &constraints,
+            Size::new(200.0, 100.0),
+            &[("a", Size::new(20.0, 10.0))],
+        );
+
+        assert!(errors.is_empty());
+        assert_bounds(
+            &solver,
+            "a",
+            Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)),
+        );
+    }
+
+    #[test]
+    fn duplicate_and_reserved_names_are_reported() {
+        let (solver, errors) = solve(&["a", "a", PARENT], &[], Size::new(10.0, 10.0), &[]);
+
+        assert_eq!(
+            errors,
+            vec![
+                ConstraintLayoutError::DuplicateName("a".to_string()),
+                ConstraintLayoutError::ReservedName,
+            ]
+        );
+        assert_eq!(solver.children.len(), 1);
+    }
+
+    #[test]
+    fn unsatisfiable_and_unknown_constraints_are_left_out() {
+        let constraints = [
+            anchor("a", Anchor::Width).equals(10.0),
+            anchor("a", Anchor::Width).equals(20.0),
+            anchor("c", Anchor::Left).equals(0.0),
+        ];
+        let (solver, errors) = solve(&["a"], &constraints, Size::new(100.0, 100.0), &[]);
+
+        assert_eq!(
+            errors,
+            vec![
+                ConstraintLayoutError::Unsatisfiable { constraint: 1 },
+                ConstraintLayoutError::UnknownName {
+                    constraint: 2,
+                    name: "c".to_string(),
+                },
+            ]
+        );
+        assert_eq!(solver.bounds("a").unwrap().size().width, 10.0);
+    }
+}
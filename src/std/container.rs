@@ -1,7 +1,13 @@
 use std::rc::Rc;
 
 use crate::{
-    canvas::{color::Color, paint::Paint},
+    canvas::{
+        color::{Color, Color32f},
+        nine_patch::NinePatch,
+        paint::Paint,
+        path::Path,
+        shadow::Shadow,
+    },
     geo::{Point, Rect, Size},
     painter::Painter,
     user_interface::ui_state::UIState,
@@ -10,11 +16,25 @@ use crate::{
 
 pub struct ContainerPainter {
     color: Option<Color>,
+    shadow: Option<Shadow>,
+    nine_patch: Option<NinePatch>,
 }
 
 impl Painter for ContainerPainter {
     fn paint(&self, paint_ctx: &crate::painter::PaintCtx, canvas: &mut dyn crate::canvas::Canvas) {
-        if let Some(color) = &self.color {
+        if let Some(shadow) = &self.shadow {
+            let mut path = Path::new();
+            path.add_rect(Rect::new_from_size(paint_ctx.local_bounds().size()));
+            canvas.draw_shadow(&path, shadow);
+        }
+
+        if let Some(nine_patch) = &self.nine_patch {
+            nine_patch.draw(
+                canvas,
+                &Rect::new_from_size(paint_ctx.local_bounds().size()),
+                None,
+            )
+        } else if let Some(color) = &self.color {
             let paint = Paint::new(*color);
             canvas.draw_rect(
                 &Rect::new_from_size(paint_ctx.local_bounds().size()),
@@ -30,6 +50,8 @@ pub struct Container {
     child: Child,
     color: Option<Color>,
     padding: Option<Insets>,
+    shadow: Option<Shadow>,
+    nine_patch: Option<NinePatch>,
 }
 
 impl Container {
@@ -43,6 +65,8 @@ impl Container {
             child: Rc::new(child),
             color: None,
             padding: None,
+            shadow: None,
+            nine_patch: None,
         }
     }
 
@@ -65,6 +89,29 @@ impl Container {
         self.padding = Some(padding);
         self
     }
+
+    /// Draws the skin as the background instead of the color.
+    pub fn with_nine_patch(mut self, nine_patch: NinePatch) -> Self {
+        self.nine_patch = Some(nine_patch);
+        self
+    }
+
+    /// Draws the shadow behind the container, it can reach outside of its bounds.
+    pub fn with_shadow(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    /// Lifts the container above its background by the elevation, in points, with a soft shadow
+    /// that grows and moves down as the elevation increases.
+    pub fn with_elevation(self, elevation: f32) -> Self {
+        let elevation = elevation.max(0.0);
+        self.with_shadow(
+            Shadow::new(Color32f::new(0.0, 0.0, 0.0, 0.3))
+                .with_offset(Point::new(0.0, elevation / 2.0))
+                .with_blur_radius(elevation * 2.0),
+        )
+    }
 }
 
 impl Widget for Container {
@@ -73,7 +120,11 @@ impl Widget for Container {
     }
 
     fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
-        Some(Box::new(ContainerPainter { color: self.color }))
+        Some(Box::new(ContainerPainter {
+            color: self.color,
+            shadow: self.shadow,
+            nine_patch: self.nine_patch.clone(),
+        }))
     }
 
     fn calculate_size(
@@ -107,7 +158,10 @@ impl Widget for Container {
         size: Size,
         children: &[usize],
     ) {
-        let padding = self.padding.unwrap_or_default();
+        let padding = self
+            .padding
+            .unwrap_or_default()
+            .resolve(layout_ctx.text_direction());
         let child_size = layout_ctx.preferred_size(
             children[0],
             &BoxConstraints::new()
@@ -136,3 +190,41 @@ impl Widget for Container {
         layout_ctx.set_child_bounds(children[0], child_bounds);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ContainerPainter;
+    use crate::{
+        canvas::{
+            color::Color32f, display_list::DrawCommand, paint::Paint,
+            recording_canvas::RecordingCanvas,
+        },
+        geo::{Point, Rect, Size},
+        painter::{PaintCtx, Painter},
+    };
+
+    #[test]
+    fn draws_its_color_over_its_bounds() {
+        let color = Color32f::new_rgb(0.0, 0.0, 1.0).into();
+        let painter = ContainerPainter {
+            color: Some(color),
+            shadow: None,
+            nine_patch: None,
+        };
+        let global_bounds = Rect::new(Point::new(5.0, 5.0), Size::new(20.0, 10.0));
+        let local_bounds = Rect::new_from_size(Size::new(20.0, 10.0));
+        let mut canvas = RecordingCanvas::new();
+        painter.paint(
+            &PaintCtx::new(&global_bounds, &local_bounds, None),
+            &mut canvas,
+        );
+
+        assert_eq!(
+            canvas.display_list().commands(),
+            &[DrawCommand::DrawRect {
+                rect: Rect::new_from_size(Size::new(20.0, 10.0)),
+                paint: Paint::new(color),
+            }]
+        );
+    }
+}
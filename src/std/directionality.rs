@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use crate::{
+    geo::{Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{
+        constraints::BoxConstraints, style::TextDirection, BuildCtx, Child, Children, LayoutCtx,
+        SizeCtx, Widget,
+    },
+};
+
+/// Sets the text direction for its child and everything below it,
+/// overriding the direction of the window.
+pub struct Directionality {
+    child: Child,
+    text_direction: TextDirection,
+}
+
+impl Directionality {
+    pub fn new<C>(text_direction: TextDirection, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            text_direction,
+        }
+    }
+}
+
+impl Widget for Directionality {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        build_ctx.set_child_text_direction(self.text_direction);
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        size_ctx.preferred_size(children[0], constraints)
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        layout_ctx.set_child_bounds(children[0], Rect::new_from_size(size))
+    }
+}
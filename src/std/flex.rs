@@ -4,6 +4,8 @@ use crate::{
     widget::{constraints::BoxConstraints, BuildCtx, Children, LayoutCtx, SizeCtx, Widget},
 };
 
+/// Places its children next to each other, from the start to the end edge.
+/// For right to left text the first child is on the right.
 pub struct Row {
     children: Box<dyn Fn() -> Children>,
 }
@@ -59,19 +61,32 @@ impl Widget for Row {
         let left_over_width = size.width - constrained_width;
         let unconstrained_child_width = left_over_width / (unconstrained_children as f32).max(1.0);
 
+        let right_to_left = layout_ctx.text_direction().is_right_to_left();
+        // The position of a child of the given width that starts at x from the start edge.
+        let position = |x: f32, width: f32| {
+            if right_to_left {
+                size.width - x - width
+            } else {
+                x
+            }
+        };
+
         let mut x = 0.0;
         for (id, child_size) in &child_sizes {
             if let Some(child_size) = child_size {
                 layout_ctx.set_child_position(
                     *id,
-                    Point::new(x, size.height / 2.0 - child_size.height / 2.0),
+                    Point::new(
+                        position(x, child_size.width),
+                        size.height / 2.0 - child_size.height / 2.0,
+                    ),
                 );
                 x += child_size.width;
             } else {
                 layout_ctx.set_child_bounds(
                     *id,
                     Rect::new(
-                        Point::new(x, 0.0),
+                        Point::new(position(x, unconstrained_child_width), 0.0),
                         Size::new(unconstrained_child_width, size.height),
                     ),
                 );
@@ -0,0 +1,110 @@
+use std::rc::Rc;
+
+use crate::{
+    geo::{Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{
+        constraints::BoxConstraints, style::Alignment, BuildCtx, Child, Children, LayoutCtx,
+        SizeCtx, Widget,
+    },
+};
+
+/// Sizes its child to a fraction of the space it is given.
+/// On an axis without a factor the child gets its preferred size.
+/// The child is positioned inside the available space with an alignment, centered by default.
+pub struct FractionallySizedBox {
+    child: Child,
+    width_factor: Option<f32>,
+    height_factor: Option<f32>,
+    alignment: Alignment,
+}
+
+impl FractionallySizedBox {
+    pub fn new<C>(child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            width_factor: None,
+            height_factor: None,
+            alignment: Alignment::CENTER,
+        }
+    }
+
+    pub fn with_width_factor(mut self, width_factor: f32) -> Self {
+        self.width_factor = Some(width_factor);
+        self
+    }
+
+    pub fn with_height_factor(mut self, height_factor: f32) -> Self {
+        self.height_factor = Some(height_factor);
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: impl Into<Alignment>) -> Self {
+        self.alignment = alignment.into();
+        self
+    }
+
+    fn child_constraints(&self, size: Size) -> BoxConstraints {
+        let mut constraints = BoxConstraints::new_with_max(size.width, size.height);
+        if let Some(width_factor) = self.width_factor {
+            constraints = constraints
+                .with_min_width(size.width * width_factor)
+                .with_max_width(size.width * width_factor);
+        }
+
+        if let Some(height_factor) = self.height_factor {
+            constraints = constraints
+                .with_min_height(size.height * height_factor)
+                .with_max_height(size.height * height_factor);
+        }
+
+        constraints
+    }
+}
+
+impl Widget for FractionallySizedBox {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        if constraints.has_max() {
+            return Some(constraints.max_size());
+        }
+
+        // A fraction of an unbounded axis is meaningless, so we shrink to the child instead.
+        let child_size = size_ctx.preferred_size(children[0], constraints)?;
+        Some(Size::new(
+            constraints.max_width().unwrap_or(child_size.width),
+            constraints.max_height().unwrap_or(child_size.height),
+        ))
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        let child_constraints = self.child_constraints(size);
+        let child_size = layout_ctx
+            .preferred_size(children[0], &child_constraints)
+            .map(|child_size| child_constraints.constrain(child_size))
+            .unwrap_or_else(|| child_constraints.max_size());
+
+        let alignment = self.alignment.resolve(layout_ctx.text_direction());
+        layout_ctx.set_child_bounds(
+            children[0],
+            Rect::new(alignment.position(child_size, size), child_size),
+        )
+    }
+}
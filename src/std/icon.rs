@@ -0,0 +1,157 @@
+use std::{any::Any, sync::Arc, time::Duration};
+
+use crate::{
+    animation::animation_event::AnimationEvent,
+    canvas::{
+        color::{Color, Color32f},
+        image_cache::ImageSource,
+        paint::Paint,
+        svg::{load_svg_in_background, Svg, SvgLoad},
+        Canvas,
+    },
+    event_context::EventCtx,
+    geo::{Rect, Size},
+    painter::{PaintCtx, Painter},
+    std::image::ImageFit,
+    user_interface::{ui_ctx::UIContext, ui_state::UIState},
+    widget::{
+        constraints::BoxConstraints, style::Alignment, BuildCtx, Children, LayoutCtx, SizeCtx,
+        Widget,
+    },
+};
+
+// How often a loading icon checks whether its document finished loading
+const LOAD_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Clone)]
+enum IconState {
+    Loading(SvgLoad),
+    Loaded(Svg),
+    Failed,
+}
+
+/// Shows an SVG icon, tinted with a single color. The icon is square, as large as its size
+/// unless the constraints say otherwise. The document is loaded in the background, an icon that
+/// is loading or can't be loaded leaves its space empty.
+pub struct Icon {
+    source: ImageSource,
+    size: f32,
+    color: Option<Color>,
+}
+
+impl Icon {
+    pub const DEFAULT_SIZE: f32 = 24.0;
+
+    pub fn new(source: ImageSource) -> Self {
+        Self {
+            source,
+            size: Self::DEFAULT_SIZE,
+            color: Some(Color32f::new_grey(1.0).into()),
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(ImageSource::file(path))
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Draws the icon in the colors of the document instead of tinting it.
+    pub fn with_original_colors(mut self) -> Self {
+        self.color = None;
+        self
+    }
+}
+
+impl Widget for Icon {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        if let Some(IconState::Loading(_)) = build_ctx.state::<IconState>() {
+            build_ctx.request_widget_animation(0, LOAD_POLL_INTERVAL);
+        }
+        vec![]
+    }
+
+    fn state(&self, _ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
+        let load = load_svg_in_background(&self.source);
+        let state = match load.result() {
+            Some(Ok(svg)) => IconState::Loaded(svg),
+            Some(Err(_)) => IconState::Failed,
+            None => IconState::Loading(load),
+        };
+        Some(Arc::new(state))
+    }
+
+    fn animation_event(
+        &self,
+        event_context: &mut EventCtx,
+        ui_ctx: &mut UIContext,
+        _ui_state: &UIState,
+    ) {
+        let end = matches!(event_context.animation_event(), AnimationEvent::End(_));
+        let state = match ui_ctx.state::<IconState>() {
+            Some(IconState::Loading(load)) => match load.result() {
+                Some(Ok(svg)) => IconState::Loaded(svg),
+                Some(Err(_)) => IconState::Failed,
+                // Still loading, the rebuild requests the next animation
+                None if end => IconState::Loading(load.clone()),
+                None => return,
+            },
+            _ => return,
+        };
+        ui_ctx.set_state(move |_: &IconState| state.clone());
+    }
+
+    fn calculate_size(
+        &self,
+        _children: &[usize],
+        constraints: &BoxConstraints,
+        _size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        Some(constraints.constrain(Size::new(self.size, self.size)))
+    }
+
+    fn layout(&self, _ui_state: &UIState, _: &mut LayoutCtx, _: Size, _: &[usize]) {}
+
+    fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(IconPainter {
+            paint: self.color.map(Paint::new),
+        }))
+    }
+}
+
+pub struct IconPainter {
+    paint: Option<Paint>,
+}
+
+impl Painter for IconPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        if let Some(IconState::Loaded(svg)) = paint_ctx.state::<IconState>() {
+            let bounds = paint_ctx.local_bounds().size();
+            draw_icon(
+                canvas,
+                svg,
+                &Rect::new_from_size(bounds),
+                self.paint.as_ref(),
+            );
+        }
+    }
+}
+
+/// Draws the icon as large as it fits into the rect and centered in it, keeping its aspect ratio.
+pub fn draw_icon(canvas: &mut dyn Canvas, svg: &Svg, rect: &Rect, paint: Option<&Paint>) {
+    let size = ImageFit::Contain.fitted_size(svg.size(), rect.size());
+    let position = Alignment::CENTER.position(size, rect.size());
+    canvas.draw_svg(
+        svg,
+        &Rect::new(rect.position(), size).with_offset(position),
+        paint,
+    );
+}
@@ -0,0 +1,397 @@
+use std::{
+    any::Any,
+    rc::Rc,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use crate::{
+    animation::animation_event::AnimationEvent,
+    canvas::{
+        image::{self, Sampling},
+        image_cache::{image_cache, load_in_background, ImageLoad, ImageSource},
+        Canvas,
+    },
+    event_context::EventCtx,
+    geo::{Rect, Size},
+    painter::{PaintCtx, Painter},
+    user_interface::{ui_ctx::UIContext, ui_state::UIState},
+    widget::{
+        constraints::BoxConstraints, style::Alignment, BuildCtx, Child, Children, LayoutCtx,
+        SizeCtx, Widget,
+    },
+};
+
+/// How an image is sized into the bounds of the `Image` widget.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ImageFit {
+    /// As large as possible while the whole image stays visible, keeping its aspect ratio.
+    #[default]
+    Contain,
+    /// As small as possible while the image covers the bounds, keeping its aspect ratio.
+    /// What doesn't fit is cut off.
+    Cover,
+    /// Stretches the image to the bounds, ignoring its aspect ratio.
+    Fill,
+    /// Keeps the size of the image, what doesn't fit is cut off.
+    None,
+    /// Like `Contain`, but never larger than the image.
+    ScaleDown,
+}
+
+impl ImageFit {
+    /// The size the image is drawn at in the bounds.
+    pub fn fitted_size(&self, image: Size, bounds: Size) -> Size {
+        if image.width <= 0.0 || image.height <= 0.0 {
+            return Size::new(0.0, 0.0);
+        }
+
+        let contain = (bounds.width / image.width).min(bounds.height / image.height);
+        let scale = match self {
+            ImageFit::Contain => contain,
+            ImageFit::Cover => (bounds.width / image.width).max(bounds.height / image.height),
+            ImageFit::Fill => return bounds,
+            ImageFit::None => 1.0,
+            ImageFit::ScaleDown => contain.min(1.0),
+        };
+        Size::new(image.width * scale, image.height * scale)
+    }
+}
+
+// How often a widget animation checks whether the image, or its scaled copy, finished loading.
+const LOAD_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Clone)]
+enum ImageState {
+    Loading(ImageLoad),
+    Loaded(LoadedImage),
+    Failed,
+}
+
+// The image and the copy of it that is scaled to the size it is drawn at. Layout requests the
+// copy, clones of the state share it so that the painter draws it once it is loaded.
+#[derive(Clone)]
+struct LoadedImage {
+    image: image::Image,
+    scaled: Arc<Mutex<ScaledCopy>>,
+}
+
+enum ScaledCopy {
+    // Not laid out yet
+    Unknown,
+    // Drawn at its own size or larger, there is no copy
+    Unscaled,
+    Loading((u32, u32), ImageLoad),
+}
+
+impl LoadedImage {
+    fn new(image: image::Image) -> Self {
+        Self {
+            image,
+            scaled: Arc::new(Mutex::new(ScaledCopy::Unknown)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<ScaledCopy> {
+        self.scaled.lock().expect("Scaled image lock poisoned")
+    }
+
+    // Loads a copy at the size in pixels, unless it is already loading or loaded.
+    fn request_scaled(&self, source: &ImageSource, pixels: Option<(u32, u32)>) {
+        let mut scaled = self.lock();
+        match (pixels, &*scaled) {
+            (Some(pixels), ScaledCopy::Loading(requested, _)) if *requested == pixels => (),
+            (Some(pixels), _) => {
+                *scaled = ScaledCopy::Loading(pixels, load_in_background(source, Some(pixels)))
+            }
+            (None, _) => *scaled = ScaledCopy::Unscaled,
+        }
+    }
+
+    // Whether the widget waits for layout or for the copy to finish loading.
+    fn is_waiting(&self) -> bool {
+        match &*self.lock() {
+            ScaledCopy::Unknown => true,
+            ScaledCopy::Unscaled => false,
+            ScaledCopy::Loading(_, load) => load.result().is_none(),
+        }
+    }
+
+    // The copy at the size in pixels, once it is loaded.
+    fn scaled(&self, pixels: (u32, u32)) -> Option<image::Image> {
+        match &*self.lock() {
+            ScaledCopy::Loading(requested, load) if *requested == pixels => {
+                load.result().and_then(Result::ok)
+            }
+            _ => None,
+        }
+    }
+}
+
+// The size in pixels of the copy the image is drawn from, none when it isn't scaled down.
+fn scaled_pixels(
+    fit: ImageFit,
+    image: &image::Image,
+    bounds: Size,
+    dpi: f32,
+) -> Option<(u32, u32)> {
+    let size = fit.fitted_size(image.size(), bounds);
+    let pixels = (
+        (size.width * dpi).round() as u32,
+        (size.height * dpi).round() as u32,
+    );
+    (pixels.0 > 0 && pixels.0 < image.width() && pixels.1 > 0).then_some(pixels)
+}
+
+/// Shows an image from a file or from encoded bytes. Images are loaded through the shared
+/// `image_cache`, an image that isn't in the cache yet is loaded on a background thread while the
+/// placeholder is shown. If loading fails the error child is shown. When the image is laid out
+/// smaller than it is, a copy scaled to its size on the screen is loaded in the background too,
+/// and the image is drawn from the copy once it is loaded.
+pub struct Image {
+    source: ImageSource,
+    fit: ImageFit,
+    alignment: Alignment,
+    sampling: Sampling,
+    placeholder: Option<Child>,
+    error: Option<Child>,
+}
+
+impl Image {
+    pub fn new(source: ImageSource) -> Self {
+        Self {
+            source,
+            fit: ImageFit::default(),
+            alignment: Alignment::CENTER,
+            sampling: Sampling::default(),
+            placeholder: None,
+            error: None,
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(ImageSource::file(path))
+    }
+
+    pub fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Where the image is placed in the bounds when it doesn't fill them, the center by default.
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Shown in the bounds of the image while it is loading.
+    pub fn with_placeholder<C>(mut self, placeholder: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        self.placeholder = Some(Rc::new(placeholder));
+        self
+    }
+
+    /// Shown in the bounds of the image when it couldn't be loaded.
+    pub fn with_error<C>(mut self, error: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        self.error = Some(Rc::new(error));
+        self
+    }
+}
+
+impl Widget for Image {
+    fn state(&self, _ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
+        let cached = image_cache().get(&self.source, None);
+        let state = match cached {
+            Some(image) => ImageState::Loaded(LoadedImage::new(image)),
+            None => ImageState::Loading(load_in_background(&self.source, None)),
+        };
+        Some(Arc::new(state))
+    }
+
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        let child = match build_ctx.state::<ImageState>() {
+            Some(ImageState::Loading(_)) => {
+                build_ctx.request_widget_animation(0, LOAD_POLL_INTERVAL);
+                self.placeholder.as_ref()
+            }
+            Some(ImageState::Failed) => self.error.as_ref(),
+            Some(ImageState::Loaded(loaded)) => {
+                if loaded.is_waiting() {
+                    build_ctx.request_widget_animation(0, LOAD_POLL_INTERVAL);
+                }
+                None
+            }
+            None => None,
+        };
+
+        child
+            .map(|child| vec![child(build_ctx.ui_state())])
+            .unwrap_or_default()
+    }
+
+    fn animation_event(
+        &self,
+        event_context: &mut EventCtx,
+        ui_ctx: &mut UIContext,
+        _ui_state: &UIState,
+    ) {
+        let end = matches!(event_context.animation_event(), AnimationEvent::End(_));
+        let state = match ui_ctx.state::<ImageState>() {
+            Some(ImageState::Loading(load)) => match load.result() {
+                Some(Ok(image)) => ImageState::Loaded(LoadedImage::new(image)),
+                Some(Err(_)) => ImageState::Failed,
+                // Still loading, the rebuild requests the next animation
+                None if end => ImageState::Loading(load.clone()),
+                None => return,
+            },
+            // Setting the state again either polls once more or, when the copy is loaded,
+            // sends it to the painter, which redraws the image from it
+            Some(ImageState::Loaded(loaded))
+                if end && !matches!(*loaded.lock(), ScaledCopy::Unscaled) =>
+            {
+                ImageState::Loaded(loaded.clone())
+            }
+            _ => return,
+        };
+        ui_ctx.set_state(move |_: &ImageState| state.clone());
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        match size_ctx.state::<ImageState>() {
+            // As large as the image, scaled down to the constraints keeping the aspect ratio
+            Some(ImageState::Loaded(loaded)) => {
+                let size = loaded.image.size();
+                let max = Size::new(
+                    constraints.max_width().unwrap_or(f32::MAX),
+                    constraints.max_height().unwrap_or(f32::MAX),
+                );
+                Some(constraints.constrain(ImageFit::ScaleDown.fitted_size(size, max)))
+            }
+            _ => match children.first() {
+                Some(child) => size_ctx.preferred_size(*child, constraints),
+                None => Some(constraints.constrain(Size::new(0.0, 0.0))),
+            },
+        }
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        if let Some(child) = children.first() {
+            layout_ctx.set_child_bounds(*child, Rect::new_from_size(size))
+        }
+
+        // Images that are scaled down are drawn from a copy at their size in pixels
+        if let Some(ImageState::Loaded(loaded)) = layout_ctx.state::<ImageState>() {
+            let pixels = scaled_pixels(self.fit, &loaded.image, size, layout_ctx.dpi());
+            loaded.request_scaled(&self.source, pixels);
+        }
+    }
+
+    fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(ImagePainter {
+            fit: self.fit,
+            alignment: self.alignment,
+            sampling: self.sampling,
+        }))
+    }
+}
+
+pub struct ImagePainter {
+    fit: ImageFit,
+    alignment: Alignment,
+    sampling: Sampling,
+}
+
+impl Painter for ImagePainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        if let Some(ImageState::Loaded(loaded)) = paint_ctx.state::<ImageState>() {
+            let image = &loaded.image;
+            let bounds = paint_ctx.local_bounds().size();
+            let size = self.fit.fitted_size(image.size(), bounds);
+            let position = self
+                .alignment
+                .resolve(paint_ctx.text_direction())
+                .position(size, bounds);
+
+            // Until the copy that layout requested is loaded the whole image is scaled while drawing
+            let scaled = scaled_pixels(self.fit, image, bounds, paint_ctx.dpi())
+                .and_then(|pixels| loaded.scaled(pixels));
+
+            // Cover and None can reach outside of the bounds
+            canvas.clip_rect(&Rect::new_from_size(bounds));
+            canvas.draw_image_rect(
+                scaled.as_ref().unwrap_or(image),
+                None,
+                &Rect::new(position, size),
+                self.sampling,
+                None,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImageFit;
+    use crate::geo::Size;
+
+    const IMAGE: Size = Size {
+        width: 200.0,
+        height: 100.0,
+    };
+
+    #[test]
+    fn fitted_size_keeps_the_aspect_ratio() {
+        let bounds = Size::new(100.0, 100.0);
+        assert_eq!(
+            ImageFit::Contain.fitted_size(IMAGE, bounds),
+            Size::new(100.0, 50.0)
+        );
+        assert_eq!(
+            ImageFit::Cover.fitted_size(IMAGE, bounds),
+            Size::new(200.0, 100.0)
+        );
+        assert_eq!(ImageFit::Fill.fitted_size(IMAGE, bounds), bounds);
+        assert_eq!(ImageFit::None.fitted_size(IMAGE, bounds), IMAGE);
+    }
+
+    #[test]
+    fn scale_down_never_enlarges() {
+        assert_eq!(
+            ImageFit::ScaleDown.fitted_size(IMAGE, Size::new(400.0, 400.0)),
+            IMAGE
+        );
+        assert_eq!(
+            ImageFit::ScaleDown.fitted_size(IMAGE, Size::new(100.0, 100.0)),
+            Size::new(100.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn empty_images_have_no_size() {
+        assert_eq!(
+            ImageFit::Contain.fitted_size(Size::new(0.0, 10.0), Size::new(100.0, 100.0)),
+            Size::new(0.0, 0.0)
+        );
+    }
+}
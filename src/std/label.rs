@@ -1,16 +1,34 @@
-use std::{any::Any, sync::Arc};
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+};
 
 use crate::{
-    canvas::{color::Color32f, font::Font, paint::Paint, text::Text, Canvas},
+    canvas::{
+        color::Color32f,
+        font::Font,
+        paragraph::{Paragraph, ParagraphStyle},
+        text::TextAlignment,
+        Canvas,
+    },
     event_context::EventCtx,
-    geo::{Rect, Size},
+    geo::{Point, Rect, Size},
     painter::{PaintCtx, Painter},
     user_interface::{ui_ctx::UIContext, ui_state::UIState, value::Value},
-    widget::{constraints::BoxConstraints, BuildCtx, Children, SizeCtx, Widget},
+    widget::{
+        constraints::BoxConstraints,
+        style::{HorizontalAlignment, VerticalAlignment},
+        BuildCtx, Children, SizeCtx, Widget,
+    },
 };
 
+/// Text that wraps to the width it is given. It is measured with the text measurer of the
+/// user interface, the painter lays it out as a `Paragraph` with the measurer it is given.
 pub struct Label {
     text: Value,
+    font: Font,
+    style: ParagraphStyle,
+    vertical_alignment: VerticalAlignment,
 }
 
 pub fn label_with_bind(name: &str) -> Box<Label> {
@@ -19,69 +37,150 @@ pub fn label_with_bind(name: &str) -> Box<Label> {
 
 impl Label {
     pub fn new(text: impl Into<Value>) -> Self {
-        Self { text: text.into() }
+        Self {
+            text: text.into(),
+            font: Font::default(),
+            style: ParagraphStyle::new().with_color(Color32f::new_grey(1.0)),
+            vertical_alignment: VerticalAlignment::Top,
+        }
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the alignment, line limit and color of the text.
+    pub fn with_style(mut self, style: ParagraphStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Aligns the lines inside the label, this sets the alignment of the style.
+    /// `Start` and `End` follow the text direction the label inherits.
+    pub fn with_horizontal_alignment(mut self, alignment: HorizontalAlignment) -> Self {
+        self.style = self.style.with_align(alignment.into());
+        self
+    }
+
+    /// Where the text is placed when the label is taller than its text. Text that wraps is centered as a block.
+    pub fn with_vertical_alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.vertical_alignment = alignment;
+        self
     }
 }
 
 impl Widget for Label {
     fn build(&self, build_ctx: &mut BuildCtx) -> Children {
-        if let Value::Binding(binding) = &self.text {
-            build_ctx.bind(binding);
+        for binding in self.text.binds() {
+            build_ctx.bind(&binding);
         }
 
         vec![]
     }
 
     fn binding_changed(&self, event_context: &mut EventCtx, ui_ctx: &mut UIContext) {
-        if let Value::Binding(_) = &self.text {
-            let text = event_context.binding().map(|text| text.to_string());
+        // Localized text depends on the locale and its arguments, so it is formatted again
+        let text = match &self.text {
+            Value::Binding(_) => event_context.binding().map(|text| text.to_string()),
+            Value::Const(_) => None,
+            Value::Localized(_) => Some(self.text.var(event_context.ui_state()).to_string()),
+        };
 
-            if let Some(text) = text {
-                ui_ctx.set_state(move |_old_state| Text::new(&text, Font::new("Arial", 24.0)));
-            }
+        if let Some(text) = text {
+            ui_ctx.set_state(move |_old_state: &String| text.clone());
         }
     }
 
     fn calculate_size(
         &self,
         _children: &[usize],
-        _constraints: &BoxConstraints,
+        constraints: &BoxConstraints,
         size_ctx: &SizeCtx,
     ) -> Option<Size> {
-        size_ctx.state::<Text>().map(|state| state.bounds().size())
+        size_ctx.state::<String>().map(|text| {
+            let style = self
+                .style
+                .clone()
+                .with_text_direction(size_ctx.text_direction());
+            let size = size_ctx.text_measurer().measure_paragraph(
+                text,
+                &self.font,
+                &style,
+                constraints.max_width().unwrap_or(f32::INFINITY),
+            );
+            constraints.constrain(size)
+        })
     }
 
     fn state(&self, ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
         let text = match &self.text {
             Value::Binding(name) => ui_state.get(name).map(|text| text.to_string()),
             Value::Const(text) => Some(text.to_string()),
+            Value::Localized(_) => Some(self.text.var(ui_state).to_string()),
         };
 
-        if let Some(text) = text {
-            let font = Font::new("Arial", 24.0);
-            Some(Arc::new(Text::new(&text, font)))
-        } else {
-            None
-        }
+        text.map(|text| Arc::new(text) as Arc<dyn Any + Send>)
     }
 
     fn painter(&self, _: &UIState) -> Option<Box<dyn Painter>> {
-        Some(Box::new(LabelPainter {}))
+        Some(Box::new(LabelPainter {
+            font: self.font.clone(),
+            style: self.style.clone(),
+            vertical_alignment: self.vertical_alignment,
+            paragraph: Mutex::new(None),
+        }))
     }
 }
 
-pub struct LabelPainter {}
+pub struct LabelPainter {
+    font: Font,
+    style: ParagraphStyle,
+    vertical_alignment: VerticalAlignment,
+    // Laid out once for the text in the state, again when the state gets another text
+    // or the direction the label inherits changes.
+    paragraph: Mutex<Option<Paragraph>>,
+}
 
 impl Painter for LabelPainter {
     fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
-        let paint = Paint::new(Color32f::new_grey(1.0));
-
-        if let Some(state) = paint_ctx.state::<Text>() {
-            canvas.draw_text(
-                state,
-                &Rect::new_from_size(paint_ctx.local_bounds().size()),
-                &paint,
-            )
+        if let Some(text) = paint_ctx.state::<String>() {
+            let size = paint_ctx.local_bounds().size();
+            let style = self
+                .style
+                .clone()
+                .with_text_direction(paint_ctx.text_direction());
+            let mut paragraph = self
+                .paragraph
+                .lock()
+                .expect("Label paragraph lock poisoned");
+            let current = paragraph.as_ref().is_some_and(|paragraph| {
+                paragraph.text() == text.as_str() && paragraph.style() == &style
+            });
+            if !current {
+                *paragraph = Some(
+                    paint_ctx
+                        .text_measurer()
+                        .layout_paragraph(text, &self.font, &style, size.width),
+                );
+            }
+            let Some(paragraph) = paragraph.as_ref() else {
+                return;
+            };
+            paragraph.layout(size.width);
+
+            let metrics = paragraph.text_metrics();
+            let y = if self.vertical_alignment == VerticalAlignment::Center
+                && paragraph.line_count() > 1
+            {
+                (size.height - metrics.size().height) / 2.0
+            } else {
+                let alignment =
+                    TextAlignment::new(HorizontalAlignment::Left, self.vertical_alignment);
+                alignment.origin(&Rect::new_from_size(size), &metrics).y - metrics.ascent
+            };
+
+            canvas.draw_paragraph(&Point::new(0.0, y), paragraph)
         }
     }
 }
@@ -1,12 +1,28 @@
+pub mod align;
 pub mod animated_builder;
 pub mod animated_color_box;
+pub mod aspect_ratio;
 pub mod center;
+pub mod clip;
+pub mod constrained_box;
+pub mod constraint_layout;
 pub mod container;
+pub mod directionality;
 pub mod drag_source;
 pub mod drop_target;
 pub mod flex;
+pub mod fractionally_sized_box;
+pub mod icon;
+pub mod image;
 pub mod label;
 pub mod list;
+pub mod opacity;
+pub mod padding;
+pub mod rich_text;
+pub mod selectable_text;
 pub mod sized_box;
+pub mod stack;
 pub mod text_button;
+pub mod transform;
 pub mod viewport;
+pub mod virtual_list;
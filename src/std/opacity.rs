@@ -0,0 +1,106 @@
+use std::{any::Any, rc::Rc, sync::Arc};
+
+use crate::{
+    canvas::{layer::Layer, Canvas},
+    event_context::EventCtx,
+    geo::{Rect, Size},
+    painter::{PaintCtx, Painter},
+    user_interface::{
+        ui_ctx::UIContext,
+        ui_state::UIState,
+        value::{Value, Var},
+    },
+    widget::{constraints::BoxConstraints, BuildCtx, Child, Children, LayoutCtx, SizeCtx, Widget},
+};
+
+/// Paints its child and everything below it into a layer that is faded as a whole,
+/// so overlapping descendants don't show through each other.
+pub struct Opacity {
+    child: Child,
+    opacity: Value,
+}
+
+impl Opacity {
+    /// The opacity goes from 0, invisible, to 1, fully opaque. Bind it to a real to fade the
+    /// child, a binding that isn't a real shows the child fully opaque.
+    pub fn new<C>(opacity: impl Into<Value>, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            opacity: opacity.into(),
+        }
+    }
+}
+
+fn opacity(var: &Var) -> f32 {
+    var.as_real().unwrap_or(1.0).clamp(0.0, 1.0)
+}
+
+impl Widget for Opacity {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        for binding in self.opacity.binds() {
+            build_ctx.bind(&binding);
+        }
+
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn state(&self, ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
+        Some(Arc::new(opacity(&self.opacity.var(ui_state))))
+    }
+
+    fn binding_changed(&self, event_context: &mut EventCtx, ui_ctx: &mut UIContext) {
+        let opacity = opacity(&self.opacity.var(event_context.ui_state()));
+        ui_ctx.set_state(move |_: &f32| opacity);
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        size_ctx.preferred_size(children[0], constraints)
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        layout_ctx.set_child_bounds(children[0], Rect::new_from_size(size))
+    }
+
+    fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(OpacityPainter))
+    }
+}
+
+/// Paints with the opacity in the state of the widget, so it follows its binding.
+pub struct OpacityPainter;
+
+impl OpacityPainter {
+    fn opacity(paint_ctx: &PaintCtx) -> f32 {
+        paint_ctx.state::<f32>().copied().unwrap_or(1.0)
+    }
+}
+
+impl Painter for OpacityPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        // A fully opaque subtree doesn't need the cost of a layer
+        let opacity = Self::opacity(paint_ctx);
+        if opacity < 1.0 {
+            canvas.save_layer(&Layer::new().with_alpha(opacity));
+        }
+    }
+
+    fn paint_after_children(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        if Self::opacity(paint_ctx) < 1.0 {
+            canvas.restore();
+        }
+    }
+}
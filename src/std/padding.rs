@@ -0,0 +1,77 @@
+use std::rc::Rc;
+
+use crate::{
+    geo::{Point, Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{
+        constraints::BoxConstraints, style::Insets, BuildCtx, Child, Children, LayoutCtx, SizeCtx,
+        Widget,
+    },
+};
+
+/// Insets its child by the given padding, start and end insets follow the text direction.
+pub struct Padding {
+    child: Child,
+    padding: Insets,
+}
+
+impl Padding {
+    pub fn new<C>(padding: Insets, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            padding,
+        }
+    }
+}
+
+impl Widget for Padding {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        let padding = self.padding.resolve(size_ctx.text_direction());
+        let horizontal = padding.left + padding.right;
+        let vertical = padding.top + padding.bottom;
+        let child_size =
+            size_ctx.preferred_size(children[0], &constraints.shrunk(horizontal, vertical));
+
+        if let Some(child_size) = child_size {
+            Some(constraints.constrain(Size::new(
+                child_size.width + horizontal,
+                child_size.height + vertical,
+            )))
+        } else if constraints.has_max() {
+            Some(constraints.max_size())
+        } else {
+            None
+        }
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        let padding = self.padding.resolve(layout_ctx.text_direction());
+        let child_size = Size::new(
+            (size.width - padding.left - padding.right).max(0.0),
+            (size.height - padding.top - padding.bottom).max(0.0),
+        );
+
+        layout_ctx.set_child_bounds(
+            children[0],
+            Rect::new(Point::new(padding.left, padding.top), child_size),
+        )
+    }
+}
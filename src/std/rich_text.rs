@@ -0,0 +1,228 @@
+use std::{any::Any, sync::Arc};
+
+use crate::{
+    app::event::MouseEvent,
+    canvas::{
+        color::Color32f,
+        font::Font,
+        paragraph::ParagraphStyle,
+        rich_text::{self, SpanStyle, TextSpan},
+        Canvas,
+    },
+    event_context::EventCtx,
+    geo::{Point, Size},
+    painter::{PaintCtx, Painter},
+    user_interface::{ui_ctx::UIContext, ui_state::UIState, value::Value},
+    widget::{
+        constraints::BoxConstraints, message_context::ApplicationCtx, BuildCtx, Children, SizeCtx,
+        Widget,
+    },
+};
+
+use super::text_button::ClickHandler;
+
+/// A piece of `RichText` with its own style, optionally bound to the `UIState` and clickable.
+pub struct Span {
+    text: Value,
+    style: SpanStyle,
+    click_handler: ClickHandler,
+}
+
+impl Span {
+    pub fn new(text: impl Into<Value>) -> Self {
+        Self {
+            text: text.into(),
+            style: SpanStyle::new(),
+            click_handler: None,
+        }
+    }
+
+    pub fn with_style(mut self, style: SpanStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn on_click<F>(mut self, click_handler: F) -> Self
+    where
+        F: Fn(&mut ApplicationCtx) + 'static,
+    {
+        self.click_handler = Some(Box::new(click_handler));
+        self
+    }
+}
+
+/// Text made of spans with different fonts, colors and decorations, wrapped like a `Label`.
+pub struct RichText {
+    spans: Vec<Span>,
+    font: Font,
+    style: ParagraphStyle,
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Self {
+            spans: Vec::new(),
+            font: Font::default(),
+            style: ParagraphStyle::new().with_color(Color32f::new_grey(1.0)),
+        }
+    }
+
+    /// The font of spans that don't set their own.
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn with_style(mut self, style: ParagraphStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn text_spans(&self, ui_state: &UIState) -> Vec<TextSpan> {
+        self.spans
+            .iter()
+            .map(|span| {
+                let text = match &span.text {
+                    Value::Binding(name) => ui_state
+                        .get(name)
+                        .map(|text| text.to_string())
+                        .unwrap_or_default(),
+                    text => text.var(ui_state).to_string(),
+                };
+                TextSpan::new(text, span.style.clone())
+            })
+            .collect()
+    }
+}
+
+impl Default for RichText {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The laid out spans, and the span the mouse went down on. A span is clicked when the mouse
+// goes up on the span it went down on.
+#[derive(Clone)]
+struct RichTextState {
+    rich_text: Arc<rich_text::RichText>,
+    pressed: Option<usize>,
+}
+
+impl RichTextState {
+    fn new(rich_text: rich_text::RichText) -> Self {
+        Self {
+            rich_text: Arc::new(rich_text),
+            pressed: None,
+        }
+    }
+
+    fn with_pressed(&self, pressed: Option<usize>) -> Self {
+        Self {
+            rich_text: self.rich_text.clone(),
+            pressed,
+        }
+    }
+}
+
+impl Widget for RichText {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        for binding in self.spans.iter().flat_map(|span| span.text.binds()) {
+            build_ctx.bind(&binding);
+        }
+
+        vec![]
+    }
+
+    fn state(&self, ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
+        Some(Arc::new(RichTextState::new(rich_text::RichText::new(
+            &self.text_spans(ui_state),
+            self.font.clone(),
+            self.style.clone(),
+        ))))
+    }
+
+    fn binding_changed(&self, event_context: &mut EventCtx, ui_ctx: &mut UIContext) {
+        let spans = self.text_spans(event_context.ui_state());
+        let font = self.font.clone();
+        let style = self.style.clone();
+        ui_ctx.set_state(move |_old_state: &RichTextState| {
+            RichTextState::new(rich_text::RichText::new(
+                &spans,
+                font.clone(),
+                style.clone(),
+            ))
+        });
+    }
+
+    fn calculate_size(
+        &self,
+        _children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        size_ctx.state::<RichTextState>().map(|state| {
+            let rich_text = &state.rich_text;
+            rich_text.set_text_direction(size_ctx.text_direction());
+            rich_text.layout(constraints.max_width().unwrap_or(f32::INFINITY));
+            constraints.constrain(rich_text.size())
+        })
+    }
+
+    fn mouse_event(
+        &self,
+        _ui_state: &UIState,
+        event_ctx: &mut EventCtx,
+        ui_ctx: &mut UIContext,
+        message_ctx: &mut ApplicationCtx,
+    ) {
+        let (released, event) = match event_ctx.mouse_event() {
+            MouseEvent::MouseDown(event) => (false, event),
+            MouseEvent::MouseUp(event) => (true, event),
+            _ => return,
+        };
+        let Some((span, pressed)) = ui_ctx.state::<RichTextState>().map(|state| {
+            state.rich_text.set_text_direction(ui_ctx.text_direction());
+            (
+                state.rich_text.hit_test(event.local_position()),
+                state.pressed,
+            )
+        }) else {
+            return;
+        };
+
+        if !released {
+            ui_ctx.set_state(move |old_state: &RichTextState| old_state.with_pressed(span));
+            return;
+        }
+
+        if pressed.is_some() {
+            ui_ctx.set_state(|old_state: &RichTextState| old_state.with_pressed(None));
+        }
+        let clicked = span.filter(|span| pressed == Some(*span));
+        if let Some(handler) = clicked.and_then(|span| self.spans[span].click_handler.as_ref()) {
+            (handler)(message_ctx)
+        }
+    }
+
+    fn painter(&self, _: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(RichTextPainter {}))
+    }
+}
+
+pub struct RichTextPainter {}
+
+impl Painter for RichTextPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        if let Some(RichTextState { rich_text, .. }) = paint_ctx.state::<RichTextState>() {
+            rich_text.set_text_direction(paint_ctx.text_direction());
+            rich_text.layout(paint_ctx.local_bounds().size().width);
+            canvas.draw_paragraph(&Point::new(0.0, 0.0), rich_text.paragraph())
+        }
+    }
+}
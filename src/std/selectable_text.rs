@@ -0,0 +1,379 @@
+use std::{any::Any, ops::Range, sync::Arc};
+
+use winit::event::VirtualKeyCode;
+
+use crate::{
+    app::{clipboard::clipboard, event::MouseEvent},
+    canvas::{
+        color::{Color, Color32f},
+        font::Font,
+        paint::Paint,
+        paragraph::{Paragraph, ParagraphStyle},
+        Canvas,
+    },
+    event_context::EventCtx,
+    geo::{Point, Size},
+    painter::{PaintCtx, Painter},
+    user_interface::{ui_ctx::UIContext, ui_state::UIState, value::Value},
+    widget::{
+        constraints::BoxConstraints, message_context::ApplicationCtx, BuildCtx, Children, SizeCtx,
+        Widget,
+    },
+};
+
+/// What a drag extends the selection by, it depends on how often the text was clicked.
+#[derive(Clone, Copy, PartialEq)]
+enum Granularity {
+    Character,
+    Word,
+    Line,
+}
+
+pub struct SelectableTextState {
+    paragraph: Arc<Paragraph>,
+    // The selection reaches from the range that was pressed to the range under the mouse,
+    // both are empty when selecting by character. Ranges are in UTF-16 code units.
+    anchor: Range<usize>,
+    extent: Range<usize>,
+    granularity: Granularity,
+    selecting: bool,
+}
+
+impl SelectableTextState {
+    fn new(paragraph: Paragraph) -> Self {
+        Self {
+            paragraph: Arc::new(paragraph),
+            anchor: 0..0,
+            extent: 0..0,
+            granularity: Granularity::Character,
+            selecting: false,
+        }
+    }
+
+    /// The selected range in UTF-16 code units, empty if nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.anchor.start.min(self.extent.start)..self.anchor.end.max(self.extent.end)
+    }
+
+    pub fn selected_text(&self) -> String {
+        self.paragraph.text_in_range(self.selection())
+    }
+
+    fn range_at(&self, index: usize) -> Range<usize> {
+        match self.granularity {
+            Granularity::Character => index..index,
+            Granularity::Word => self.paragraph.word_at(index),
+            Granularity::Line => self.paragraph.line_at(index),
+        }
+    }
+
+    fn with_selection(&self, anchor: Range<usize>, extent: Range<usize>) -> Self {
+        Self {
+            paragraph: self.paragraph.clone(),
+            anchor,
+            extent,
+            granularity: self.granularity,
+            selecting: self.selecting,
+        }
+    }
+
+    // Starts selecting at the index, or with `extend` moves the end of the selection there.
+    fn pressed(&self, index: usize, granularity: Granularity, extend: bool) -> Self {
+        let mut state = self.with_selection(0..0, 0..0);
+        state.granularity = granularity;
+        state.selecting = true;
+        let range = state.range_at(index);
+        state.anchor = if extend {
+            self.anchor.clone()
+        } else {
+            range.clone()
+        };
+        state.extent = range;
+        state
+    }
+
+    fn dragged(&self, index: usize) -> Self {
+        self.with_selection(self.anchor.clone(), self.range_at(index))
+    }
+
+    fn with_all_selected(&self) -> Self {
+        self.with_selection(0..0, 0..self.paragraph.utf16_len())
+    }
+
+    // Leaves the clipboard as it is when nothing is selected.
+    fn copy_selection(&self) {
+        let text = self.selected_text();
+        if !text.is_empty() {
+            clipboard().set_text(&text);
+        }
+    }
+}
+
+/// Text like a `Label` that can be selected with the mouse and copied with the shortcut for copy.
+/// A double click selects a word, a triple click the line, dragging after that extends the selection
+/// by words or lines.
+pub struct SelectableText {
+    text: Value,
+    font: Font,
+    style: ParagraphStyle,
+    selection_color: Color,
+}
+
+impl SelectableText {
+    pub fn new(text: impl Into<Value>) -> Self {
+        Self {
+            text: text.into(),
+            font: Font::default(),
+            style: ParagraphStyle::new().with_color(Color32f::new_grey(1.0)),
+            selection_color: Color32f::new(0.2, 0.4, 0.8, 0.6).into(),
+        }
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn with_style(mut self, style: ParagraphStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The color painted behind the selected text.
+    pub fn with_selection_color(mut self, color: impl Into<Color>) -> Self {
+        self.selection_color = color.into();
+        self
+    }
+
+    fn text(&self, ui_state: &UIState) -> Option<String> {
+        match &self.text {
+            Value::Binding(name) => ui_state.get(name).map(|text| text.to_string()),
+            Value::Const(text) => Some(text.to_string()),
+            Value::Localized(_) => Some(self.text.var(ui_state).to_string()),
+        }
+    }
+
+    // The position in the text under a point in local coordinates.
+    fn index_at(ui_ctx: &UIContext, position: &Point) -> Option<usize> {
+        let width = ui_ctx.local_bounds(ui_ctx.id()).size().width;
+        ui_ctx.state::<SelectableTextState>().map(|state| {
+            state.paragraph.set_text_direction(ui_ctx.text_direction());
+            state.paragraph.layout(width);
+            state.paragraph.index_at(position)
+        })
+    }
+}
+
+impl Widget for SelectableText {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        for binding in self.text.binds() {
+            build_ctx.bind(&binding);
+        }
+
+        vec![]
+    }
+
+    fn state(&self, ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
+        self.text(ui_state).map(|text| {
+            Arc::new(SelectableTextState::new(Paragraph::new(
+                &text,
+                self.font.clone(),
+                self.style.clone(),
+            ))) as Arc<dyn Any + Send>
+        })
+    }
+
+    fn binding_changed(&self, event_context: &mut EventCtx, ui_ctx: &mut UIContext) {
+        // The selection doesn't survive a change of the text
+        if let Some(text) = self.text(event_context.ui_state()) {
+            let font = self.font.clone();
+            let style = self.style.clone();
+            ui_ctx.set_state(move |_old_state: &SelectableTextState| {
+                SelectableTextState::new(Paragraph::new(&text, font.clone(), style.clone()))
+            });
+        }
+    }
+
+    fn calculate_size(
+        &self,
+        _children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        size_ctx.state::<SelectableTextState>().map(|state| {
+            state
+                .paragraph
+                .set_text_direction(size_ctx.text_direction());
+            state
+                .paragraph
+                .layout(constraints.max_width().unwrap_or(f32::INFINITY));
+            constraints.constrain(state.paragraph.size())
+        })
+    }
+
+    fn mouse_event(
+        &self,
+        _ui_state: &UIState,
+        event_ctx: &mut EventCtx,
+        ui_ctx: &mut UIContext,
+        _message_ctx: &mut ApplicationCtx,
+    ) {
+        match event_ctx.mouse_event() {
+            MouseEvent::MouseDown(event) => {
+                let Some(index) = Self::index_at(ui_ctx, event.local_position()) else {
+                    return;
+                };
+                let granularity = match event.click_count() {
+                    1 => Granularity::Character,
+                    2 => Granularity::Word,
+                    _ => Granularity::Line,
+                };
+                let extend = granularity == Granularity::Character && event.is_shift_down();
+
+                ui_ctx.request_focus();
+                ui_ctx.set_state(move |old_state: &SelectableTextState| {
+                    old_state.pressed(index, granularity, extend)
+                });
+            }
+            MouseEvent::MouseDragStart(event) | MouseEvent::MouseDrag(event) => {
+                let selecting = ui_ctx
+                    .state::<SelectableTextState>()
+                    .map_or(false, |state| state.selecting);
+                if !selecting {
+                    return;
+                }
+
+                if let Some(index) = Self::index_at(ui_ctx, event.local_position()) {
+                    ui_ctx
+                        .set_state(move |old_state: &SelectableTextState| old_state.dragged(index));
+                }
+            }
+            MouseEvent::MouseUp(_) => {
+                ui_ctx.set_state(|old_state: &SelectableTextState| {
+                    let mut state = old_state
+                        .with_selection(old_state.anchor.clone(), old_state.extent.clone());
+                    state.selecting = false;
+                    state
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn key_event(
+        &self,
+        _ui_state: &UIState,
+        event_ctx: &mut EventCtx,
+        ui_ctx: &mut UIContext,
+        _message_ctx: &mut ApplicationCtx,
+    ) {
+        let event = event_ctx.key_event();
+        if event.is_shortcut(VirtualKeyCode::C) {
+            if let Some(state) = ui_ctx.state::<SelectableTextState>() {
+                state.copy_selection();
+            }
+        } else if event.is_shortcut(VirtualKeyCode::A) {
+            ui_ctx.set_state(|old_state: &SelectableTextState| old_state.with_all_selected());
+        }
+    }
+
+    fn focus_changed(&self, focused: bool, ui_ctx: &mut UIContext) {
+        if !focused {
+            ui_ctx
+                .set_state(|old_state: &SelectableTextState| old_state.with_selection(0..0, 0..0));
+        }
+    }
+
+    fn painter(&self, _: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(SelectableTextPainter {
+            selection_paint: Paint::new(self.selection_color),
+        }))
+    }
+}
+
+pub struct SelectableTextPainter {
+    selection_paint: Paint,
+}
+
+impl Painter for SelectableTextPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        if let Some(state) = paint_ctx.state::<SelectableTextState>() {
+            state
+                .paragraph
+                .set_text_direction(paint_ctx.text_direction());
+            state
+                .paragraph
+                .layout(paint_ctx.local_bounds().size().width);
+
+            let selection = state.selection();
+            if !selection.is_empty() {
+                for rect in state.paragraph.rects_for_range(selection) {
+                    canvas.draw_rect(&rect, &self.selection_paint);
+                }
+            }
+
+            canvas.draw_paragraph(&Point::new(0.0, 0.0), &state.paragraph)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Granularity, SelectableTextState};
+    use crate::{
+        app::clipboard::{clipboard, set_clipboard, LocalClipboard},
+        canvas::{
+            font::Font,
+            paragraph::{Paragraph, ParagraphStyle},
+        },
+    };
+
+    fn state(text: &str) -> SelectableTextState {
+        SelectableTextState::new(Paragraph::new(text, Font::default(), ParagraphStyle::new()))
+    }
+
+    #[test]
+    fn clicks_select_by_character_word_or_line() {
+        let state = state("first line\nsecond line");
+
+        let pressed = state.pressed(2, Granularity::Character, false);
+        assert_eq!(pressed.selection(), 2..2);
+        assert_eq!(pressed.dragged(8).selected_text(), "rst li");
+
+        let pressed = state.pressed(2, Granularity::Word, false);
+        assert_eq!(pressed.selected_text(), "first");
+        assert_eq!(pressed.dragged(8).selected_text(), "first line");
+
+        let pressed = state.pressed(13, Granularity::Line, false);
+        assert_eq!(pressed.selected_text(), "second line");
+        assert_eq!(
+            pressed.dragged(2).selected_text(),
+            "first line\nsecond line"
+        );
+    }
+
+    #[test]
+    fn shift_click_extends_the_selection() {
+        let state = state("first line")
+            .pressed(2, Granularity::Character, false)
+            .pressed(8, Granularity::Character, true);
+        assert_eq!(state.selection(), 2..8);
+
+        let state = state.pressed(1, Granularity::Character, true);
+        assert_eq!(state.selection(), 1..2);
+    }
+
+    #[test]
+    fn select_all_and_copy() {
+        set_clipboard(LocalClipboard::default());
+
+        let state = state("a😀b");
+        state.copy_selection();
+        assert_eq!(clipboard().text(), None);
+
+        let state = state.with_all_selected();
+        assert_eq!(state.selection(), 0..4);
+        state.copy_selection();
+        assert_eq!(clipboard().text(), Some("a😀b".to_string()));
+    }
+}
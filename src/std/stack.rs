@@ -0,0 +1,246 @@
+use std::{any::Any, rc::Rc};
+
+use crate::{
+    geo::{Point, Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{
+        constraints::BoxConstraints, style::Alignment, BuildCtx, Child, Children, LayoutCtx,
+        SizeCtx, Widget,
+    },
+};
+
+/// A widget that paints its children on top of each other, in the order they are built.
+/// The stack sizes itself to its largest non-positioned child.
+/// Non-positioned children are placed according to the alignment of the stack,
+/// children wrapped in a `Positioned` are placed relative to the edges of the stack.
+pub struct Stack {
+    children: Box<dyn Fn() -> Children>,
+    alignment: Alignment,
+}
+
+impl Stack {
+    pub fn new<F>(children: F) -> Self
+    where
+        F: Fn() -> Children + 'static,
+    {
+        Self {
+            children: Box::new(children),
+            alignment: Alignment::TOP_LEFT,
+        }
+    }
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+}
+
+impl Widget for Stack {
+    fn build(&self, _build_ctx: &mut BuildCtx) -> Children {
+        (self.children)()
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        let child_constraints = constraints.loosened();
+        let mut size: Option<Size> = None;
+        for child in children {
+            if size_ctx.parent_data::<StackPosition>(*child).is_some() {
+                continue;
+            }
+
+            if let Some(child_size) = size_ctx.preferred_size(*child, &child_constraints) {
+                size = Some(match size {
+                    Some(size) => Size::new(
+                        size.width.max(child_size.width),
+                        size.height.max(child_size.height),
+                    ),
+                    None => child_size,
+                });
+            }
+        }
+
+        // Without any sized, non-positioned children the stack takes all the space it is given.
+        Some(size.unwrap_or_else(|| constraints.max_size()))
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        let child_constraints = BoxConstraints::new_with_max(size.width, size.height);
+        let alignment = self.alignment.resolve(layout_ctx.text_direction());
+        for child in children {
+            let position = layout_ctx.parent_data::<StackPosition>(*child).copied();
+            let bounds = if let Some(position) = position {
+                position.resolve(
+                    size,
+                    alignment,
+                    layout_ctx.preferred_size(*child, &child_constraints),
+                )
+            } else {
+                let child_size = layout_ctx
+                    .preferred_size(*child, &child_constraints)
+                    .unwrap_or(size);
+                Rect::new(alignment.position(child_size, size), child_size)
+            };
+
+            layout_ctx.set_child_bounds(*child, bounds);
+        }
+    }
+}
+
+/// The position of a child inside a `Stack`, relative to the edges of the stack.
+/// Edges that are not set fall back to the stack's alignment.
+#[derive(Default, Clone, Copy)]
+pub struct StackPosition {
+    pub left: Option<f32>,
+    pub top: Option<f32>,
+    pub right: Option<f32>,
+    pub bottom: Option<f32>,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+}
+
+impl StackPosition {
+    fn resolve(&self, stack_size: Size, alignment: Alignment, preferred: Option<Size>) -> Rect {
+        let preferred = preferred.unwrap_or(stack_size);
+        let (x, width) = Self::resolve_axis(
+            self.left,
+            self.right,
+            self.width,
+            preferred.width,
+            stack_size.width,
+            alignment.x,
+        );
+        let (y, height) = Self::resolve_axis(
+            self.top,
+            self.bottom,
+            self.height,
+            preferred.height,
+            stack_size.height,
+            alignment.y,
+        );
+
+        Rect::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    fn resolve_axis(
+        start: Option<f32>,
+        end: Option<f32>,
+        extent: Option<f32>,
+        preferred: f32,
+        available: f32,
+        alignment: f32,
+    ) -> (f32, f32) {
+        match (start, end, extent) {
+            (Some(start), Some(end), _) => (start, (available - start - end).max(0.0)),
+            (Some(start), None, extent) => (start, extent.unwrap_or(preferred)),
+            (None, Some(end), extent) => {
+                let extent = extent.unwrap_or(preferred);
+                (available - end - extent, extent)
+            }
+            (None, None, extent) => {
+                let extent = extent.unwrap_or(preferred);
+                ((available - extent) * (alignment + 1.0) / 2.0, extent)
+            }
+        }
+    }
+}
+
+/// Places its child at a fixed position inside a `Stack`.
+/// Positioned children do not contribute to the size of the stack.
+pub struct Positioned {
+    child: Child,
+    position: StackPosition,
+}
+
+impl Positioned {
+    pub fn new<C>(child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            position: StackPosition::default(),
+        }
+    }
+
+    /// A positioned child that covers the entire stack.
+    pub fn fill<C>(child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self::new(child)
+            .with_left(0.0)
+            .with_top(0.0)
+            .with_right(0.0)
+            .with_bottom(0.0)
+    }
+
+    pub fn with_left(mut self, left: f32) -> Self {
+        self.position.left = Some(left);
+        self
+    }
+
+    pub fn with_top(mut self, top: f32) -> Self {
+        self.position.top = Some(top);
+        self
+    }
+
+    pub fn with_right(mut self, right: f32) -> Self {
+        self.position.right = Some(right);
+        self
+    }
+
+    pub fn with_bottom(mut self, bottom: f32) -> Self {
+        self.position.bottom = Some(bottom);
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.position.width = Some(width);
+        self
+    }
+
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.position.height = Some(height);
+        self
+    }
+}
+
+impl Widget for Positioned {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        size_ctx.preferred_size(children[0], constraints)
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        layout_ctx.set_child_bounds(children[0], Rect::new_from_size(size))
+    }
+
+    fn parent_data(&self) -> Option<&dyn Any> {
+        Some(&self.position)
+    }
+}
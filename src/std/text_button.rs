@@ -1,30 +1,68 @@
 use crate::{
     app::event::MouseEvent,
-    canvas::{color::Color32f, font::Font, paint::Paint, Canvas},
+    canvas::{
+        color::Color32f,
+        font::Font,
+        image_cache::ImageSource,
+        nine_patch::NinePatch,
+        paint::Paint,
+        svg::{svg_cache, Svg},
+        text::TextAlignment,
+        Canvas,
+    },
     event_context::EventCtx,
-    geo::{Rect, Size},
+    geo::{Point, Rect, Size},
     painter::{PaintCtx, Painter},
+    std::icon::draw_icon,
     user_interface::{ui_ctx::UIContext, ui_state::UIState, value::Value},
     widget::{
-        constraints::BoxConstraints, message_context::ApplicationCtx, BuildCtx, Children,
-        LayoutCtx, SizeCtx, Widget,
+        constraints::BoxConstraints,
+        message_context::ApplicationCtx,
+        style::{HorizontalAlignment, VerticalAlignment},
+        BuildCtx, Children, LayoutCtx, SizeCtx, Widget,
     },
 };
 use std::{any::Any, sync::Arc};
 
+#[derive(Clone, Copy)]
 enum ButtonState {
     Active,
     Inactive,
     Hovered,
 }
 
+// The icon is loaded once when the state is created, an icon that fails to load stays left out.
+#[derive(Clone)]
+struct TextButtonState {
+    button: ButtonState,
+    icon: Option<Svg>,
+}
+
+impl TextButtonState {
+    fn with_button(&self, button: ButtonState) -> Self {
+        Self {
+            button,
+            icon: self.icon.clone(),
+        }
+    }
+}
+
 pub type ClickHandler = Option<Box<dyn Fn(&mut ApplicationCtx)>>;
 
 pub struct TextButton {
     text: Value,
+    font: Font,
+    alignment: TextAlignment,
+    icon: Option<ImageSource>,
+    skin: Option<NinePatch>,
+    hover_skin: Option<NinePatch>,
+    active_skin: Option<NinePatch>,
     click_handler: ClickHandler,
 }
 
+// The gap between the icon and the text, relative to the height of the text.
+const ICON_GAP: f32 = 0.3;
+
 pub fn text_button<F>(text: impl Into<Value>, on_click: F) -> Box<TextButton>
 where
     F: Fn(&mut ApplicationCtx) + 'static,
@@ -36,10 +74,65 @@ impl TextButton {
     pub fn new(text: impl Into<Value>) -> Self {
         Self {
             text: text.into(),
+            font: Font::default(),
+            alignment: TextAlignment::default(),
+            icon: None,
+            skin: None,
+            hover_skin: None,
+            active_skin: None,
             click_handler: None,
         }
     }
 
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Where the text is placed when the button is wider than its text, centered by default.
+    /// `Start` and `End` follow the text direction of the button.
+    pub fn with_horizontal_alignment(mut self, alignment: HorizontalAlignment) -> Self {
+        self.alignment.horizontal = alignment;
+        self
+    }
+
+    pub fn with_vertical_alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.alignment.vertical = alignment;
+        self
+    }
+
+    /// Shows the SVG icon before the text, as high as the text and in the color of the text.
+    /// For right to left text the icon is on the right of the text.
+    pub fn with_icon(mut self, icon: ImageSource) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Draws the button with the skin instead of a flat rounded rect, in every state that doesn't
+    /// have a skin of its own.
+    pub fn with_skin(mut self, skin: NinePatch) -> Self {
+        self.skin = Some(skin);
+        self
+    }
+
+    pub fn with_hover_skin(mut self, skin: NinePatch) -> Self {
+        self.hover_skin = Some(skin);
+        self
+    }
+
+    /// The skin while the button is pressed.
+    pub fn with_active_skin(mut self, skin: NinePatch) -> Self {
+        self.active_skin = Some(skin);
+        self
+    }
+
+    // An icon that can't be loaded is left out, the button still works with its text.
+    fn icon(&self) -> Option<Svg> {
+        self.icon
+            .as_ref()
+            .and_then(|icon| svg_cache().load(icon).ok())
+    }
+
     pub fn on_click<F>(mut self, click_handler: F) -> Self
     where
         F: Fn(&mut ApplicationCtx) + 'static,
@@ -50,35 +143,38 @@ impl TextButton {
 }
 
 impl Widget for TextButton {
-    fn build(&self, _build_ctx: &mut BuildCtx) -> Children {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        for binding in self.text.binds() {
+            build_ctx.bind(&binding);
+        }
+
         vec![]
     }
 
+    // Rebuilds the button, which creates a painter with the new text.
+    fn binding_changed(&self, _event_context: &mut EventCtx, ui_ctx: &mut UIContext) {
+        ui_ctx.set_state(|state: &TextButtonState| state.with_button(ButtonState::Inactive))
+    }
+
     fn calculate_size(
         &self,
         _children: &[usize],
         _constraints: &BoxConstraints,
-        _layout_ctx: &SizeCtx,
+        size_ctx: &SizeCtx,
     ) -> Option<Size> {
-        let font = Font::new("Arial", 24.0);
-        let font = skia_safe::Font::new(
-            skia_safe::Typeface::new(font.typeface(), skia_safe::FontStyle::normal()).unwrap(),
-            font.size(),
-        );
-        let blob = match &self.text {
-            Value::Binding(_) => None,
-            Value::Const(str) => skia_safe::TextBlob::new(str.to_string(), &font),
-        };
-
-        if let Some(blob) = blob {
-            let bounds = blob.bounds();
-            Some(Size::new(
-                bounds.width() + bounds.width() * 0.1,
-                bounds.height() + bounds.height() * 0.1,
-            ))
+        let size = size_ctx.measure_text(&self.text, &self.font)?.size();
+        let has_icon = size_ctx
+            .state::<TextButtonState>()
+            .is_some_and(|state| state.icon.is_some());
+        let icon_width = if has_icon {
+            size.height * (1.0 + ICON_GAP)
         } else {
-            None
-        }
+            0.0
+        };
+        Some(Size::new(
+            size.width + size.width * 0.1 + icon_width,
+            size.height + size.height * 0.1,
+        ))
     }
 
     fn layout(&self, _ui_state: &UIState, _: &mut LayoutCtx, _: Size, _: &[usize]) {}
@@ -91,26 +187,39 @@ impl Widget for TextButton {
         message_ctx: &mut ApplicationCtx,
     ) {
         match event_ctx.mouse_event() {
-            MouseEvent::MouseMove(_) => ui_ctx.set_state(|_| ButtonState::Hovered),
-            MouseEvent::MouseDown(_) => ui_ctx.set_state(|_| ButtonState::Active),
+            MouseEvent::MouseMove(_) => {
+                ui_ctx.set_state(|state: &TextButtonState| state.with_button(ButtonState::Hovered))
+            }
+            MouseEvent::MouseDown(_) => {
+                ui_ctx.set_state(|state: &TextButtonState| state.with_button(ButtonState::Active))
+            }
             MouseEvent::MouseUp(_) => {
                 if let Some(handler) = &self.click_handler {
                     (handler)(message_ctx)
                 }
 
-                ui_ctx.set_state(|_| ButtonState::Inactive)
+                ui_ctx.set_state(|state: &TextButtonState| state.with_button(ButtonState::Inactive))
             }
             _ => (),
         }
     }
 
     fn state(&self, _: &UIState) -> Option<Arc<dyn Any + Send>> {
-        Some(Arc::new(ButtonState::Inactive))
+        Some(Arc::new(TextButtonState {
+            button: ButtonState::Inactive,
+            icon: self.icon(),
+        }))
     }
 
     fn painter(&self, ui_state: &UIState) -> Option<Box<dyn Painter>> {
         let text = self.text.var(ui_state).to_string();
-        Some(Box::new(TextButtonPainter::new(text)))
+        Some(Box::new(
+            TextButtonPainter::new(text, self.font.clone(), self.alignment).with_skins(
+                self.skin.clone(),
+                self.hover_skin.clone().or_else(|| self.skin.clone()),
+                self.active_skin.clone().or_else(|| self.skin.clone()),
+            ),
+        ))
     }
 }
 
@@ -119,51 +228,97 @@ pub struct TextButtonPainter {
     inactive_paint: Paint,
     hover_paint: Paint,
     text: String,
+    font: Font,
+    alignment: TextAlignment,
+    inactive_skin: Option<NinePatch>,
+    hover_skin: Option<NinePatch>,
+    active_skin: Option<NinePatch>,
 }
 
 impl TextButtonPainter {
-    pub fn new(text: String) -> Self {
+    pub fn new(text: String, font: Font, alignment: TextAlignment) -> Self {
         Self {
             active_paint: Paint::new(Color32f::new_grey(0.25)),
             inactive_paint: Paint::new(Color32f::new_grey(0.05)),
             hover_paint: Paint::new(Color32f::new_grey(0.15)),
             text,
+            font,
+            alignment,
+            inactive_skin: None,
+            hover_skin: None,
+            active_skin: None,
         }
     }
+
+    pub fn with_skins(
+        mut self,
+        inactive: Option<NinePatch>,
+        hovered: Option<NinePatch>,
+        active: Option<NinePatch>,
+    ) -> Self {
+        self.inactive_skin = inactive;
+        self.hover_skin = hovered;
+        self.active_skin = active;
+        self
+    }
 }
 
 impl Painter for TextButtonPainter {
     fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
-        let state = paint_ctx.state::<ButtonState>();
+        let state = paint_ctx.state::<TextButtonState>();
         if let Some(state) = state {
-            match state {
-                ButtonState::Active => canvas.draw_rounded_rect(
-                    &Rect::new_from_size(paint_ctx.local_bounds().size()),
-                    4.0,
-                    4.0,
-                    &self.active_paint,
-                ),
-                ButtonState::Inactive => canvas.draw_rounded_rect(
-                    &Rect::new_from_size(paint_ctx.local_bounds().size()),
-                    4.0,
-                    4.0,
-                    &self.inactive_paint,
-                ),
-                ButtonState::Hovered => canvas.draw_rounded_rect(
-                    &Rect::new_from_size(paint_ctx.local_bounds().size()),
-                    4.0,
-                    4.0,
-                    &self.hover_paint,
-                ),
+            let (skin, paint) = match state.button {
+                ButtonState::Active => (&self.active_skin, &self.active_paint),
+                ButtonState::Inactive => (&self.inactive_skin, &self.inactive_paint),
+                ButtonState::Hovered => (&self.hover_skin, &self.hover_paint),
+            };
+            let rect = Rect::new_from_size(paint_ctx.local_bounds().size());
+            match skin {
+                Some(skin) => skin.draw(canvas, &rect, None),
+                None => canvas.draw_rounded_rect(&rect, 4.0, 4.0, paint),
             }
         }
 
         let text_paint = Paint::new(Color32f::new_grey(1.0));
-        canvas.draw_string(
-            &Rect::new_from_size(paint_ctx.local_bounds().size()),
-            &self.text,
-            &Font::new("Arial", 24.0),
-            &text_paint,
+        // Keep the text inside the padding that calculate_size adds around it
+        let size = paint_ctx.measure_text(&self.text, &self.font).size();
+        let padding = Size::new(size.width * 0.05, size.height * 0.05);
+        let bounds = paint_ctx.local_bounds().size();
+        let mut rect = Rect::new(
+            Point::new(padding.width, padding.height),
+            Size::new(
+                bounds.width - 2.0 * padding.width,
+                bounds.height - 2.0 * padding.height,
+            ),
         );
+        if let Some(icon) = state.and_then(|state| state.icon.as_ref()) {
+            // The icon leads the text, on the right for right to left text
+            let right_to_left = paint_ctx.text_direction().is_right_to_left();
+            let icon_x = if right_to_left {
+                bounds.width - padding.width - size.height
+            } else {
+                padding.width
+            };
+            let icon_rect = Rect::new(
+                Point::new(icon_x, (bounds.height - size.height) / 2.0),
+                Size::new(size.height, size.height),
+            );
+            draw_icon(canvas, icon, &icon_rect, Some(&text_paint));
+
+            let icon_width = size.height * (1.0 + ICON_GAP);
+            let left = if right_to_left {
+                rect.left()
+            } else {
+                rect.left() + icon_width
+            };
+            rect = Rect::new(
+                Point::new(left, rect.top()),
+                Size::new(rect.size().width - icon_width, rect.size().height),
+            );
+        }
+        let alignment = self
+            .alignment
+            .with_text_direction(paint_ctx.text_direction());
+        canvas.draw_string(&rect, &self.text, &self.font, alignment, &text_paint);
     }
 }
@@ -0,0 +1,126 @@
+use std::rc::Rc;
+
+use crate::{
+    canvas::Canvas,
+    geo::{self, Rect, Size},
+    painter::{PaintCtx, Painter},
+    user_interface::ui_state::UIState,
+    widget::{
+        constraints::BoxConstraints,
+        style::{Alignment, TextDirection},
+        BuildCtx, Child, Children, LayoutCtx, SizeCtx, Widget,
+    },
+};
+
+/// Rotates, scales or skews its child around an origin when painting. Layout is not affected,
+/// the child keeps the size and position it would have without the transform.
+/// The child stays interactive, pointer positions are mapped back through the transform.
+pub struct Transform {
+    child: Child,
+    transform: geo::Transform,
+    origin: Alignment,
+}
+
+impl Transform {
+    pub fn new<C>(transform: geo::Transform, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self {
+            child: Rc::new(child),
+            transform,
+            origin: Alignment::CENTER,
+        }
+    }
+
+    /// Rotates clockwise by the angle in degrees.
+    pub fn rotate<C>(degrees: f32, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self::new(geo::Transform::rotation(degrees), child)
+    }
+
+    pub fn scale<C>(x: f32, y: f32, child: C) -> Self
+    where
+        C: Fn(&UIState) -> Box<dyn Widget> + 'static,
+    {
+        Self::new(geo::Transform::scale(x, y), child)
+    }
+
+    /// The point in the widget that stays in place, the center by default.
+    pub fn with_origin(mut self, origin: Alignment) -> Self {
+        self.origin = origin;
+        self
+    }
+}
+
+// The transform around the origin, in the local coordinates of the widget.
+fn local_transform(
+    transform: &geo::Transform,
+    origin: &Alignment,
+    size: Size,
+    text_direction: TextDirection,
+) -> geo::Transform {
+    let origin = origin
+        .resolve(text_direction)
+        .position(Size::new(0.0, 0.0), size);
+    transform.around(origin)
+}
+
+impl Widget for Transform {
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        vec![(self.child)(build_ctx.ui_state())]
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        size_ctx.preferred_size(children[0], constraints)
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        layout_ctx.set_child_bounds(children[0], Rect::new_from_size(size))
+    }
+
+    fn transform(&self, size: Size, text_direction: TextDirection) -> Option<geo::Transform> {
+        Some(local_transform(
+            &self.transform,
+            &self.origin,
+            size,
+            text_direction,
+        ))
+    }
+
+    fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
+        Some(Box::new(TransformPainter {
+            transform: self.transform,
+            origin: self.origin,
+        }))
+    }
+}
+
+pub struct TransformPainter {
+    transform: geo::Transform,
+    origin: Alignment,
+}
+
+impl Painter for TransformPainter {
+    fn paint(&self, paint_ctx: &PaintCtx, canvas: &mut dyn Canvas) {
+        canvas.concat(&local_transform(
+            &self.transform,
+            &self.origin,
+            paint_ctx.local_bounds().size(),
+            paint_ctx.text_direction(),
+        ))
+    }
+}
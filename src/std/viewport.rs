@@ -17,11 +17,20 @@ pub struct Viewport {
     child: Child,
 }
 pub struct ViewportState {
+    // Horizontally the offset is measured from the start edge, see `mirror`.
     offset: Point,
+    // The width of the content when the offset was last set, needed to mirror the offset while building.
+    content_width: Option<f32>,
 }
+/// Scrolls its child with the mouse wheel.
+/// For right to left text the content starts scrolled to its right edge.
+/// Nested scrollables chain: the innermost one scrolls until it reaches the end of its content,
+/// the rest of the scroll is passed on to the scrollables around it.
 pub struct Scrollable {
     child: Child,
     speed: f32,
+    horizontal_snap_points: Vec<f32>,
+    vertical_snap_points: Vec<f32>,
 }
 
 impl Scrollable {
@@ -32,8 +41,84 @@ impl Scrollable {
         Self {
             child: Rc::new(child),
             speed: 5.0,
+            horizontal_snap_points: Vec::new(),
+            vertical_snap_points: Vec::new(),
         }
     }
+
+    /// Positions in the content the vertical scroll stops at, every scroll moves to the next one.
+    pub fn with_snap_points(mut self, snap_points: Vec<f32>) -> Self {
+        self.vertical_snap_points = snap_points;
+        self
+    }
+
+    pub fn with_horizontal_snap_points(mut self, snap_points: Vec<f32>) -> Self {
+        self.horizontal_snap_points = snap_points;
+        self
+    }
+
+    // Returns the part of the scroll that this scrollable uses, it only gives up scroll it can't use
+    // because it reached the end of its content.
+    fn scroll_axis(
+        &self,
+        offset: f32,
+        scroll: f32,
+        extent: f32,
+        content: f32,
+        snap_points: &[f32],
+    ) -> (f32, f32) {
+        let target = snap(offset, scroll * self.speed, snap_points);
+        let clamped = clamp_offset(target, extent, content);
+        let consumed = if clamped == target {
+            scroll
+        } else {
+            (clamped - offset) / self.speed
+        };
+
+        (clamped - offset, consumed)
+    }
+}
+
+// Moves the offset by delta, stopping at the next snap point in the direction of the scroll if there is one.
+fn snap(offset: f32, delta: f32, snap_points: &[f32]) -> f32 {
+    // A snap point at position p in the content is reached when the offset is -p.
+    let offsets = snap_points.iter().map(|position| -position);
+    let next = if delta < 0.0 {
+        offsets
+            .filter(|snap_offset| *snap_offset < offset)
+            .max_by(f32::total_cmp)
+    } else if delta > 0.0 {
+        offsets
+            .filter(|snap_offset| *snap_offset > offset)
+            .min_by(f32::total_cmp)
+    } else {
+        None
+    };
+
+    next.unwrap_or(offset + delta)
+}
+
+// Keeps the content inside of the viewport on one axis.
+fn clamp_offset(offset: f32, extent: f32, content: f32) -> f32 {
+    offset.clamp((extent - content).min(0.0), 0.0)
+}
+
+// Converts between the horizontal offset from the start edge and the position of the content.
+// Right to left content is aligned to the right edge of the viewport when the offset is zero.
+fn mirror(offset: f32, extent: f32, content: f32) -> f32 {
+    (extent - content).min(0.0) - offset
+}
+
+// The distance the content has to move to make the range start..end visible inside 0..extent.
+fn reveal(start: f32, end: f32, extent: f32) -> f32 {
+    if start < 0.0 {
+        -start
+    } else if end > extent {
+        // Keep the start visible when the range doesn't fit
+        (extent - end).max(-start)
+    } else {
+        0.0
+    }
 }
 
 impl Widget for Scrollable {
@@ -78,11 +163,37 @@ impl Widget for Scrollable {
         _message_ctx: &mut ApplicationCtx,
     ) {
         if let MouseEvent::MouseScroll(event) = event_ctx.mouse_event() {
+            let viewport = ui_ctx.child_id(0);
+            let Some(&content) = ui_ctx.children(viewport).first() else {
+                return;
+            };
+
             let scroll = event.scroll();
-            ui_ctx.send_internal_message(
-                UIMessage::new(ui_ctx.id(), ui_ctx.child_id(0), "set_offset")
-                    .with_args(vec![scroll.x * self.speed, scroll.y * self.speed]),
-            )
+            let extent = ui_ctx.local_bounds(viewport).size();
+            let content = ui_ctx.local_bounds(content);
+            let offset = content.position();
+            let (delta_x, consumed_x) = self.scroll_axis(
+                offset.x,
+                scroll.x,
+                extent.width,
+                content.size().width,
+                &self.horizontal_snap_points,
+            );
+            let (delta_y, consumed_y) = self.scroll_axis(
+                offset.y,
+                scroll.y,
+                extent.height,
+                content.size().height,
+                &self.vertical_snap_points,
+            );
+
+            if delta_x != 0.0 || delta_y != 0.0 {
+                ui_ctx.send_internal_message(
+                    UIMessage::new(ui_ctx.id(), viewport, "set_offset")
+                        .with_args(vec![delta_x, delta_y]),
+                )
+            }
+            event_ctx.consume_scroll(Point::new(consumed_x, consumed_y));
         }
     }
 
@@ -109,10 +220,31 @@ impl Widget for Viewport {
     fn state(&self, _ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
         Some(Arc::new(ViewportState {
             offset: Point::new(0.0, 0.0),
+            content_width: None,
         }))
     }
 
     fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        // Once the viewport has a size, its children know which part of them is visible.
+        let size = build_ctx.size();
+        if size.width > 0.0 && size.height > 0.0 {
+            let right_to_left = build_ctx.text_direction().is_right_to_left();
+            let origin = build_ctx.state::<ViewportState>().and_then(|state| {
+                let x = if right_to_left {
+                    // Until the content width is known, we don't know where right to left content starts
+                    mirror(state.offset.x, size.width, state.content_width?)
+                } else {
+                    state.offset.x
+                };
+                Some(Point::new(-x, -state.offset.y))
+            });
+            if let Some(origin) = origin {
+                build_ctx.set_child_visible_rect(Rect::new(origin, size));
+            }
+        }
+
+        // Scrolling rebuilds the viewport, the content keeps its element and its state
+        build_ctx.set_child_keys([0]);
         vec![(*self.child)(build_ctx.ui_state())]
     }
 
@@ -149,11 +281,13 @@ impl Widget for Viewport {
                 .with_max_width(size.width)
                 .with_max_height(size.height),
         );
+        let child_size = child_size.unwrap_or(size);
         if let Some(state) = layout_ctx.state::<ViewportState>() {
-            layout_ctx.set_child_bounds(
-                children[0],
-                Rect::new(state.offset, child_size.unwrap_or(size)),
-            );
+            let mut offset = state.offset;
+            if layout_ctx.text_direction().is_right_to_left() {
+                offset.x = mirror(offset.x, size.width, child_size.width);
+            }
+            layout_ctx.set_child_bounds(children[0], Rect::new(offset, child_size));
         }
     }
 
@@ -166,14 +300,62 @@ impl Widget for Viewport {
         let message = event_context.ui_message();
         if message.target == "set_offset" {
             assert_eq!(message.args.len(), 2);
-            let x = message.args[0].as_real().unwrap();
+            let mut x = message.args[0].as_real().unwrap();
             let y = message.args[1].as_real().unwrap();
+            // The offset is moved by the distance the content moves, which is mirrored for right to left
+            if ui_ctx.text_direction().is_right_to_left() {
+                x = -x;
+            }
+            let extent = ui_ctx.local_bounds(ui_ctx.id()).size();
+            let content = ui_ctx.local_bounds(ui_ctx.child_id(0)).size();
             ui_ctx.set_state::<ViewportState>(move |old_state| ViewportState {
-                offset: old_state.offset + Point::new(x, y),
+                offset: Point::new(
+                    clamp_offset(old_state.offset.x + x, extent.width, content.width),
+                    clamp_offset(old_state.offset.y + y, extent.height, content.height),
+                ),
+                content_width: Some(content.width),
             })
         }
     }
 
+    fn scroll_into_view(&self, rect: Rect, ui_ctx: &mut UIContext) -> Point {
+        if ui_ctx.state::<ViewportState>().is_none() {
+            return Point::default();
+        }
+
+        // Work with the position of the content, the offset in the state is mirrored for right to left
+        let extent = ui_ctx.local_bounds(ui_ctx.id()).size();
+        let content = ui_ctx.local_bounds(ui_ctx.child_id(0));
+        let offset = content.position();
+        let content = content.size();
+        let target = Point::new(
+            clamp_offset(
+                offset.x + reveal(rect.left(), rect.right(), extent.width),
+                extent.width,
+                content.width,
+            ),
+            clamp_offset(
+                offset.y + reveal(rect.top(), rect.bottom(), extent.height),
+                extent.height,
+                content.height,
+            ),
+        );
+
+        let shift = target - offset;
+        if shift.x != 0.0 || shift.y != 0.0 {
+            let mut offset = target;
+            if ui_ctx.text_direction().is_right_to_left() {
+                offset.x = mirror(offset.x, extent.width, content.width);
+            }
+            ui_ctx.set_state::<ViewportState>(move |_| ViewportState {
+                offset,
+                content_width: Some(content.width),
+            });
+        }
+
+        shift
+    }
+
     fn painter(&self, _ui_state: &UIState) -> Option<Box<dyn Painter>> {
         Some(Box::new(ViewportPainter {}))
     }
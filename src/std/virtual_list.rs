@@ -0,0 +1,244 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::BTreeMap,
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    geo::{Point, Rect, Size},
+    user_interface::ui_state::UIState,
+    widget::{constraints::BoxConstraints, BuildCtx, Children, LayoutCtx, SizeCtx, Widget},
+};
+
+// Number of rows that is built when the list doesn't know which part of it is visible.
+const INITIAL_ROWS: usize = 32;
+
+#[derive(Clone, Copy)]
+pub enum RowHeight {
+    /// Every row has exactly this height, rows are not measured.
+    Fixed(f32),
+    /// Rows are measured once they are built, rows that aren't built yet are assumed to have this height.
+    Estimated(f32),
+}
+
+impl RowHeight {
+    fn estimate(&self) -> f32 {
+        match self {
+            RowHeight::Fixed(height) | RowHeight::Estimated(height) => *height,
+        }
+    }
+}
+
+// The heights of the rows that were measured, by index. They are kept while the list scrolls,
+// so rows keep their place when they are built again.
+#[derive(Default)]
+struct RowHeights {
+    measured: Mutex<BTreeMap<usize, f32>>,
+}
+
+impl RowHeights {
+    fn record(&self, first_index: usize, heights: &[f32]) {
+        let mut measured = self.measured.lock().expect("Row heights lock poisoned");
+        for (index, height) in heights.iter().enumerate() {
+            measured.insert(first_index + index, *height);
+        }
+    }
+
+    // The top of the row, the sum of the heights of the rows before it.
+    fn top(&self, index: usize, estimate: f32) -> f32 {
+        let measured = self.measured.lock().expect("Row heights lock poisoned");
+        let correction: f32 = measured
+            .range(..index)
+            .map(|(_, height)| height - estimate)
+            .sum();
+        index as f32 * estimate + correction
+    }
+
+    // The index of the row at the height, rows between the measured ones have the estimated height.
+    fn index_at(&self, y: f32, estimate: f32) -> usize {
+        let measured = self.measured.lock().expect("Row heights lock poisoned");
+        let mut index = 0;
+        let mut top = 0.0;
+        for (measured_index, height) in measured.iter() {
+            let measured_top = top + (measured_index - index) as f32 * estimate;
+            if y < measured_top {
+                break;
+            }
+            if y < measured_top + height {
+                return *measured_index;
+            }
+            index = measured_index + 1;
+            top = measured_top + height;
+        }
+
+        index + ((y - top) / estimate).floor().max(0.0) as usize
+    }
+}
+
+/// A list that only builds the rows in and near the visible part of its enclosing `Viewport`.
+/// Rows are created on demand by calling the builder with their index. Rows are keyed by their
+/// index, so rows that stay in the visible range keep their elements and state while scrolling.
+/// Measured row heights are remembered, rows are positioned by the heights of the rows above them.
+pub struct VirtualList {
+    item_count: usize,
+    row_height: RowHeight,
+    builder: Box<dyn Fn(usize) -> Box<dyn Widget>>,
+    overscan: usize,
+    first_index: Cell<usize>,
+}
+
+impl VirtualList {
+    pub fn new<B>(item_count: usize, row_height: RowHeight, builder: B) -> Self
+    where
+        B: Fn(usize) -> Box<dyn Widget> + 'static,
+    {
+        assert!(row_height.estimate() > 0.0, "Row height must be positive");
+        Self {
+            item_count,
+            row_height,
+            builder: Box::new(builder),
+            overscan: 4,
+            first_index: Cell::new(0),
+        }
+    }
+
+    /// The number of rows that is built above and below the visible range.
+    pub fn with_overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    fn visible_range(
+        &self,
+        visible_rect: Option<Rect>,
+        heights: Option<&RowHeights>,
+    ) -> Range<usize> {
+        let Some(visible_rect) = visible_rect else {
+            return 0..INITIAL_ROWS.min(self.item_count);
+        };
+
+        let estimate = self.row_height.estimate();
+        let index_at = |y: f32| match (self.row_height, heights) {
+            (RowHeight::Estimated(_), Some(heights)) => heights.index_at(y, estimate),
+            _ => (y / estimate).floor() as usize,
+        };
+        let top = visible_rect.position().y.max(0.0);
+        let bottom = (visible_rect.position().y + visible_rect.size().height).max(top);
+        let first = index_at(top)
+            .saturating_sub(self.overscan)
+            .min(self.item_count);
+        let last = (index_at(bottom) + 1 + self.overscan).min(self.item_count);
+
+        first..last
+    }
+
+    fn row_top(&self, index: usize, heights: Option<&RowHeights>) -> f32 {
+        match (self.row_height, heights) {
+            (RowHeight::Estimated(estimate), Some(heights)) => heights.top(index, estimate),
+            (row_height, _) => index as f32 * row_height.estimate(),
+        }
+    }
+
+    // Measures the built rows and remembers their heights.
+    fn row_heights<F>(
+        &self,
+        children: &[usize],
+        heights: Option<&RowHeights>,
+        measure: F,
+    ) -> Vec<f32>
+    where
+        F: Fn(usize) -> Option<Size>,
+    {
+        let row_heights: Vec<f32> = children
+            .iter()
+            .map(|child| match self.row_height {
+                RowHeight::Fixed(height) => height,
+                RowHeight::Estimated(estimate) => {
+                    measure(*child).map(|size| size.height).unwrap_or(estimate)
+                }
+            })
+            .collect();
+
+        if let (RowHeight::Estimated(_), Some(heights)) = (self.row_height, heights) {
+            heights.record(self.first_index.get(), &row_heights);
+        }
+
+        row_heights
+    }
+}
+
+impl Widget for VirtualList {
+    fn state(&self, _ui_state: &UIState) -> Option<Arc<dyn Any + Send>> {
+        Some(Arc::new(RowHeights::default()))
+    }
+
+    fn build(&self, build_ctx: &mut BuildCtx) -> Children {
+        let range = self.visible_range(build_ctx.visible_rect(), build_ctx.state::<RowHeights>());
+        self.first_index.set(range.start);
+        build_ctx.set_child_keys(range.clone());
+        range.map(|index| (self.builder)(index)).collect()
+    }
+
+    fn calculate_size(
+        &self,
+        children: &[usize],
+        constraints: &BoxConstraints,
+        size_ctx: &SizeCtx,
+    ) -> Option<Size> {
+        let child_constraints = match constraints.max_width() {
+            Some(width) => BoxConstraints::new().with_max_width(width),
+            None => BoxConstraints::new(),
+        };
+        let measure = |child| size_ctx.preferred_size(child, &child_constraints);
+
+        // Without a width to fill, the list is as wide as its widest built row
+        let width = constraints.max_width().unwrap_or_else(|| {
+            children
+                .iter()
+                .filter_map(|child| measure(*child))
+                .map(|size| size.width)
+                .fold(0.0, f32::max)
+        });
+
+        let heights = size_ctx.state::<RowHeights>();
+        let row_heights = self.row_heights(children, heights, measure);
+
+        // Rows that aren't built count with their remembered or estimated height.
+        let height = match heights {
+            Some(heights) if matches!(self.row_height, RowHeight::Estimated(_)) => {
+                self.row_top(self.item_count, Some(heights))
+            }
+            _ => {
+                let unbuilt = self.item_count - children.len();
+                row_heights.iter().sum::<f32>() + unbuilt as f32 * self.row_height.estimate()
+            }
+        };
+
+        Some(Size::new(width, height))
+    }
+
+    fn layout(
+        &self,
+        _ui_state: &UIState,
+        layout_ctx: &mut LayoutCtx,
+        size: Size,
+        children: &[usize],
+    ) {
+        let child_constraints = BoxConstraints::new().with_max_width(size.width);
+        let heights = layout_ctx.state::<RowHeights>();
+        let row_heights = self.row_heights(children, heights, |child| {
+            layout_ctx.preferred_size(child, &child_constraints)
+        });
+
+        let mut y = self.row_top(self.first_index.get(), heights);
+        for (child, height) in children.iter().zip(row_heights) {
+            layout_ctx.set_child_bounds(
+                *child,
+                Rect::new(Point::new(0.0, y), Size::new(size.width, height)),
+            );
+            y += height;
+        }
+    }
+}
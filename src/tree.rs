@@ -10,6 +10,8 @@ pub type ElementId = usize;
 
 pub struct Tree<T: Sized> {
     nodes: HashMap<usize, Node<T>>,
+    // The parent of every child, so that looking up the parent doesn't search the whole tree.
+    parents: HashMap<ElementId, ElementId>,
     root: usize,
 }
 
@@ -17,6 +19,7 @@ impl<T> Default for Tree<T> {
     fn default() -> Self {
         Self {
             nodes: Default::default(),
+            parents: Default::default(),
             root: Default::default(),
         }
     }
@@ -26,6 +29,7 @@ impl<T> Tree<T> {
     pub fn new(data: T) -> Self {
         let mut tree = Self {
             nodes: HashMap::new(),
+            parents: HashMap::new(),
             root: 0,
         };
 
@@ -37,6 +41,7 @@ impl<T> Tree<T> {
     pub fn new_with_root_id(data: T, root: ElementId) -> Self {
         let mut tree = Self {
             nodes: HashMap::new(),
+            parents: HashMap::new(),
             root,
         };
 
@@ -47,6 +52,7 @@ impl<T> Tree<T> {
     pub fn new_with_root_node(node: Node<T>, id: ElementId) -> Self {
         let mut tree = Self {
             nodes: HashMap::new(),
+            parents: HashMap::new(),
             root: id,
         };
 
@@ -97,12 +103,39 @@ impl<T> Tree<T> {
         if let Some(parent) = self.find_parent(id) {
             self.remove_child_from_parent(parent, id)
         }
+        self.parents.remove(&id);
 
         node
     }
 
+    /// Moves the node and everything below it into a tree of its own. The parent keeps the node
+    /// as its child, so a subtree that is merged back in stays in its place among its siblings.
+    pub fn take_subtree(&mut self, id: ElementId) -> Self {
+        let mut subtree = Self {
+            nodes: HashMap::new(),
+            parents: HashMap::new(),
+            root: id,
+        };
+
+        let mut ids = vec![id];
+        while let Some(id) = ids.pop() {
+            if let Some(node) = self.nodes.remove(&id) {
+                for child in &node.children {
+                    if let Some(parent) = self.parents.remove(child) {
+                        subtree.parents.insert(*child, parent);
+                    }
+                }
+                ids.extend(&node.children);
+                subtree.nodes.insert(id, node);
+            }
+        }
+
+        subtree
+    }
+
     fn remove_children(&mut self, parent: &Node<T>) {
         for child_id in &parent.children {
+            self.parents.remove(child_id);
             if let Some(child) = self.nodes.remove(child_id) {
                 self.remove_children(&child)
             }
@@ -117,10 +150,13 @@ impl<T> Tree<T> {
         self.nodes.get_mut(&id)
     }
 
+    /// Appends the child to the children of the parent, unless it is one of them already.
     pub fn add_child(&mut self, parent: usize, child: usize) {
         if let Some(node) = self.nodes.get_mut(&parent) {
-            node.children.push(child);
-            node.children.sort();
+            if !node.children.contains(&child) {
+                node.children.push(child);
+            }
+            self.parents.insert(child, parent);
         }
     }
 
@@ -128,25 +164,21 @@ impl<T> Tree<T> {
         if let Some(node) = self.nodes.get_mut(&parent) {
             if let Some(index) = node.children.iter().position(|&element| element == child) {
                 node.children.remove(index);
+                self.parents.remove(&child);
             }
         }
     }
 
     pub fn find_parent(&self, child: usize) -> Option<usize> {
-        for (id, node) in &self.nodes {
-            if node.children.contains(&child) {
-                return Some(*id);
-            }
-        }
-
-        None
+        self.parents.get(&child).copied()
     }
 
     /// Returns all newly added element id's
     pub fn merge_subtree(&mut self, parent: ElementId, subtree: Self) -> Vec<ElementId> {
         let mut results = Vec::new();
         self.add_child(parent, subtree.root_id());
-        for (id, node) in subtree.consume_nodes() {
+        self.parents.extend(subtree.parents);
+        for (id, node) in subtree.nodes {
             self.add_node_with_id(id, node);
             results.push(id)
         }
@@ -4,23 +4,29 @@ pub mod ui_state;
 pub mod value;
 pub mod widget_tree;
 pub mod widget_tree_builder;
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use winit::window::WindowId;
+use winit::{event::ModifiersState, window::WindowId};
 
 use crate::{
     animation::animation_event::AnimationEvent,
     app::{
-        event::{ApplicationEvent, MouseEvent},
+        event::{ApplicationEvent, KeyEvent, MouseEvent},
         EventResolution, EventResponse, Senders,
     },
+    canvas::text_measurer::{SkiaTextMeasurer, TextMeasurer},
     event_context::{EventCtx, SetState, UIEvent},
-    geo::{Point, Rect, Size},
+    geo::{Point, Rect, Size, Transform},
     mouse_event::MouseEventData,
     tree::ElementId,
     widget::{
-        constraints::BoxConstraints, message_context::ApplicationCtx, BuildCtx, LayoutCtx, SizeCtx,
-        Widget,
+        constraints::BoxConstraints, message_context::ApplicationCtx, style::TextDirection,
+        BuildCtx, LayoutCtx, SizeCtx, Widget,
     },
 };
 
@@ -32,38 +38,75 @@ use self::{
     widget_tree_builder::WidgetTreeBuilder,
 };
 
+// Presses closer together than this, in time and distance, count as a double or triple click.
+const MULTI_CLICK_TIME: Duration = Duration::from_millis(500);
+const MULTI_CLICK_DISTANCE: f32 = 4.0;
+
 pub struct UserInterface {
     root_tree: WidgetTree,
+    text_measurer: Box<dyn TextMeasurer>,
     size: Size,
+    dpi: f32,
     _drag_source: Option<Box<dyn Any>>,
     mouse_position: Option<Point>,
     mouse_down_elements: Vec<ElementId>,
     dragging: bool,
     drag_data: Option<Box<dyn Any>>,
+    // The element that receives the key events.
+    focused: Option<ElementId>,
+    // Time, position and click count of the last mouse down.
+    last_click: Option<(Instant, Point, usize)>,
 }
 
 impl UserInterface {
     pub fn new(root_widget: Box<dyn Widget>, size: Size) -> Self {
+        let mut root = WidgetElement::new(root_widget);
+        root.set_visible_rect(Some(Rect::new_from_size(size)));
         Self {
-            root_tree: WidgetTree::new(WidgetElement::new(root_widget)),
+            root_tree: WidgetTree::new(root),
+            text_measurer: Box::new(SkiaTextMeasurer::new()),
             size,
+            dpi: 1.0,
             _drag_source: None,
             mouse_down_elements: Vec::new(),
             mouse_position: None,
             dragging: false,
             drag_data: None,
+            focused: None,
+            last_click: None,
         }
     }
 
+    /// Sets the text direction of the root, it is inherited by the whole tree when it is built.
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.root_tree
+            .root_mut()
+            .data
+            .set_text_direction(text_direction);
+        self
+    }
+
+    /// The scale factor of the window, it changes with the window when it is resized.
+    pub fn with_dpi(mut self, dpi: f32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Replaces the measurer that widgets size their text with, to lay out without fonts in tests for example.
+    pub fn with_text_measurer(mut self, text_measurer: impl TextMeasurer + 'static) -> Self {
+        self.text_measurer = Box::new(text_measurer);
+        self
+    }
+
     pub fn set_root_tree(&mut self, tree: WidgetTree) {
         self.root_tree = tree
     }
 
     pub fn resize(&mut self, size: Size, state: &UIState) -> HashMap<usize, (Rect, Rect)> {
         self.size = size;
-        self.root_tree
-            .root_mut()
-            .set_bounds(&Rect::new_from_size(size));
+        let root = self.root_tree.root_mut();
+        root.set_bounds(&Rect::new_from_size(size));
+        root.data.set_visible_rect(Some(Rect::new_from_size(size)));
         self.layout(state)
     }
 
@@ -74,7 +117,10 @@ impl UserInterface {
             }
 
             let widget_state = node.data.state();
-            let mut build_ctx = BuildCtx::new(id, widget_state, ui_state);
+            let mut build_ctx = BuildCtx::new(id, widget_state, ui_state)
+                .with_size(node.local_bounds.size())
+                .with_visible_rect(node.data.visible_rect())
+                .with_text_direction(node.data.text_direction());
             let children = node.data.widget().build(&mut build_ctx);
             let animation_requests = build_ctx.animation_requests();
             if !animation_requests.is_empty() {
@@ -87,10 +133,8 @@ impl UserInterface {
             if !binds.is_empty() {
                 build_result.binds.insert(id, binds);
             }
-            for child in children {
-                let child_id = self.root_tree.add_node(WidgetElement::new(child));
+            for child_id in self.root_tree.update_children(id, children, &build_ctx) {
                 self.build_element(ui_state, child_id, build_result);
-                self.root_tree.add_child(id, child_id);
             }
         } else {
             panic!()
@@ -118,7 +162,9 @@ impl UserInterface {
         state: &UIState,
         results: &mut HashMap<usize, (Rect, Rect)>,
     ) {
-        let mut layout_ctx = LayoutCtx::new(id, &self.root_tree, state);
+        let mut layout_ctx =
+            LayoutCtx::new(id, &self.root_tree, state, self.text_measurer.as_ref())
+                .with_dpi(self.dpi);
         let children = if let Some(node) = self.root_tree.get(id) {
             node.data.widget().layout(
                 state,
@@ -167,6 +213,7 @@ impl UserInterface {
         };
 
         if let Some(resize) = &response.resize {
+            self.dpi = resize.dpi;
             resolution.new_bounds = self.resize(resize.logical_size(), ui_state)
         }
 
@@ -211,28 +258,86 @@ impl UserInterface {
         intercepted: &mut Vec<ElementId>,
         hit: &mut Option<ElementId>,
     ) {
-        self.hit_test_element(self.root_tree.root_id(), position, intercepted, hit);
+        self.hit_test_element(
+            self.root_tree.root_id(),
+            position,
+            &Transform::IDENTITY,
+            intercepted,
+            hit,
+        );
     }
 
+    // The transform is accumulated on the way down, it maps window coordinates to the coordinates
+    // the element is laid out in, like `to_local_transform` without the final translation.
     fn hit_test_element(
         &self,
         id: ElementId,
         position: &Point,
+        to_layout: &Transform,
         intercepted: &mut Vec<ElementId>,
         hit: &mut Option<ElementId>,
     ) {
         let node = &self.root_tree[id];
-        if node.hit_test(position) {
+        let origin = node.global_bounds.position();
+        let layout_position = to_layout.map_point(*position);
+        if node.hit_test(&layout_position)
+            && node
+                .data
+                .widget()
+                .hit_test(&(layout_position - origin), node.global_bounds.size())
+        {
             if node.data.widget().intercept_mouse_events() {
                 intercepted.push(id);
             } else {
                 *hit = Some(id);
             }
 
+            // The transform of the element applies to its children, not to the element itself
+            let to_children = match self.element_transform(id) {
+                Some(transform) => match transform.invert() {
+                    Some(inverse) => to_layout.then(&inverse.around(origin)),
+                    None => return,
+                },
+                None => *to_layout,
+            };
             for child in node.children.iter() {
-                self.hit_test_element(*child, position, intercepted, hit)
+                self.hit_test_element(*child, position, &to_children, intercepted, hit)
+            }
+        }
+    }
+
+    // The transform that the element applies to its children, in its local coordinates.
+    fn element_transform(&self, id: ElementId) -> Option<Transform> {
+        let node = &self.root_tree[id];
+        node.data
+            .widget()
+            .transform(node.global_bounds.size(), node.data.text_direction())
+    }
+
+    /// The transform from window coordinates to the local coordinates of the element, it undoes the
+    /// transforms of the ancestors of the element. The transform of the element itself only applies
+    /// to its children, as in hit testing.
+    fn to_local_transform(&self, id: ElementId) -> Transform {
+        let mut ancestors = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.root_tree.find_parent(current) {
+            ancestors.push(parent);
+            current = parent;
+        }
+
+        let mut to_local = Transform::IDENTITY;
+        for ancestor in ancestors.into_iter().rev() {
+            if let Some(inverse) = self
+                .element_transform(ancestor)
+                .and_then(|transform| transform.invert())
+            {
+                let origin = self.root_tree[ancestor].global_bounds.position();
+                to_local = to_local.then(&inverse.around(origin));
             }
         }
+
+        let origin = self.root_tree[id].global_bounds.position();
+        to_local.then(&Transform::translation(-origin.x, -origin.y))
     }
 
     fn send_mouse_event(
@@ -243,9 +348,10 @@ impl UserInterface {
         ui_state: &UIState,
         event_response: &mut EventResponse,
         senders: Senders,
-    ) {
+    ) -> Point {
+        let mut consumed_scroll = Point::default();
         if let Some(node) = &self.root_tree.get(element_id) {
-            let local_event = event.to_local(&node.global_bounds.position());
+            let local_event = event.to_local(&self.to_local_transform(element_id));
             let state = node.data.state();
             let mut event_ctx = EventCtx::new(UIEvent::Mouse(&local_event), ui_state);
 
@@ -265,7 +371,12 @@ impl UserInterface {
             if event_ctx.drag_data.is_some() {
                 self.drag_data = event_ctx.drag_data.take()
             }
+            consumed_scroll = event_ctx.consumed_scroll();
 
+            if ui_context.focus_requested {
+                self.focused = Some(element_id);
+            }
+            let scroll_requests = ui_context.scroll_requests;
             for message in ui_context.ui_messages {
                 self.send_internal_message(
                     message.receiver,
@@ -275,6 +386,10 @@ impl UserInterface {
                 )
             }
 
+            for rect in scroll_requests {
+                self.scroll_into_view(element_id, rect, ui_state, senders.clone())
+            }
+
             // let consume = event_ctx.consume();
 
             // // animation requests
@@ -286,6 +401,8 @@ impl UserInterface {
             //     event_response.drag_widget = consume.drag_widget;
             // }
         }
+
+        consumed_scroll
     }
 
     fn send_internal_message(
@@ -303,6 +420,7 @@ impl UserInterface {
             .widget()
             .internal_event(&mut event_ctx, &mut ui_ctx, ui_state);
 
+        let scroll_requests = ui_ctx.scroll_requests;
         for message in &ui_ctx.ui_messages {
             self.send_internal_message(
                 message.receiver,
@@ -311,11 +429,52 @@ impl UserInterface {
                 senders.clone(),
             )
         }
+
+        for rect in scroll_requests {
+            self.scroll_into_view(id, rect, ui_state, senders.clone())
+        }
+    }
+
+    /// Walks up from the element and lets every ancestor scroll until the rect, in the local coordinates
+    /// of the element, is visible. Inner scrollables go first, outer ones continue with the moved rect.
+    pub fn scroll_into_view(
+        &mut self,
+        id: ElementId,
+        rect: Rect,
+        ui_state: &UIState,
+        senders: Senders,
+    ) {
+        let mut rect = rect.with_offset(self.root_tree[id].global_bounds.position());
+        let mut current = id;
+        while let Some(parent) = self.root_tree.find_parent(current) {
+            let node = &self.root_tree[parent];
+            let origin = node.global_bounds.position();
+            let state = node.data.state();
+            let mut ui_ctx =
+                UIContext::new(parent, state.as_deref(), &self.root_tree, senders.clone());
+            let shift = node.data.widget().scroll_into_view(
+                rect.with_offset(Point::new(-origin.x, -origin.y)),
+                &mut ui_ctx,
+            );
+            rect = rect.with_offset(shift);
+
+            for message in ui_ctx.ui_messages {
+                self.send_internal_message(
+                    message.receiver,
+                    UIEvent::Internal(&message),
+                    ui_state,
+                    senders.clone(),
+                )
+            }
+
+            current = parent;
+        }
     }
 
     pub fn mouse_move(
         &mut self,
         window_id: WindowId,
+        modifiers: ModifiersState,
         location: Point,
         message_ctx: &mut ApplicationCtx,
         ui_state: &UIState,
@@ -324,27 +483,36 @@ impl UserInterface {
     ) {
         self.mouse_position = Some(location);
         let event_type = if self.mouse_down_elements.is_empty() {
-            MouseEvent::MouseMove(MouseEventData::new(
-                window_id,
-                0,
-                &self.mouse_position.unwrap(),
-                &self.mouse_position.unwrap(),
-            ))
+            MouseEvent::MouseMove(
+                MouseEventData::new(
+                    window_id,
+                    0,
+                    &self.mouse_position.unwrap(),
+                    &self.mouse_position.unwrap(),
+                )
+                .with_modifiers(modifiers),
+            )
         } else if !self.dragging {
             self.dragging = true;
-            MouseEvent::MouseDragStart(MouseEventData::new(
-                window_id,
-                0,
-                &self.mouse_position.unwrap(),
-                &self.mouse_position.unwrap(),
-            ))
+            MouseEvent::MouseDragStart(
+                MouseEventData::new(
+                    window_id,
+                    0,
+                    &self.mouse_position.unwrap(),
+                    &self.mouse_position.unwrap(),
+                )
+                .with_modifiers(modifiers),
+            )
         } else {
-            MouseEvent::MouseDrag(MouseEventData::new(
-                window_id,
-                0,
-                &self.mouse_position.unwrap(),
-                &self.mouse_position.unwrap(),
-            ))
+            MouseEvent::MouseDrag(
+                MouseEventData::new(
+                    window_id,
+                    0,
+                    &self.mouse_position.unwrap(),
+                    &self.mouse_position.unwrap(),
+                )
+                .with_modifiers(modifiers),
+            )
         };
 
         let event = ApplicationEvent::Mouse(event_type);
@@ -354,23 +522,38 @@ impl UserInterface {
     pub fn mouse_down(
         &mut self,
         window_id: WindowId,
+        modifiers: ModifiersState,
         message_ctx: &mut ApplicationCtx,
         ui_state: &UIState,
         event_response: &mut EventResponse,
         senders: Senders,
     ) {
-        let event = ApplicationEvent::Mouse(MouseEvent::MouseDown(MouseEventData::new(
-            window_id,
-            0,
-            &self.mouse_position.unwrap(),
-            &self.mouse_position.unwrap(),
-        )));
+        let position = self.mouse_position.unwrap();
+        let now = Instant::now();
+        let click_count = match self.last_click {
+            Some((time, last_position, count))
+                if now.duration_since(time) < MULTI_CLICK_TIME
+                    && (position.x - last_position.x).abs() <= MULTI_CLICK_DISTANCE
+                    && (position.y - last_position.y).abs() <= MULTI_CLICK_DISTANCE =>
+            {
+                count + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some((now, position, click_count));
+
+        let event = ApplicationEvent::Mouse(MouseEvent::MouseDown(
+            MouseEventData::new(window_id, 0, &position, &position)
+                .with_modifiers(modifiers)
+                .with_click_count(click_count),
+        ));
         self.application_event(&event, message_ctx, ui_state, event_response, senders)
     }
 
     pub fn mouse_up(
         &mut self,
         window_id: WindowId,
+        modifiers: ModifiersState,
         message_ctx: &mut ApplicationCtx,
         ui_state: &UIState,
         event_response: &mut EventResponse,
@@ -378,12 +561,15 @@ impl UserInterface {
     ) {
         if self.dragging {
             self.dragging = false;
-            let event = ApplicationEvent::Mouse(MouseEvent::MouseDragEnd(MouseEventData::new(
-                window_id,
-                0,
-                &self.mouse_position.unwrap(),
-                &self.mouse_position.unwrap(),
-            )));
+            let event = ApplicationEvent::Mouse(MouseEvent::MouseDragEnd(
+                MouseEventData::new(
+                    window_id,
+                    0,
+                    &self.mouse_position.unwrap(),
+                    &self.mouse_position.unwrap(),
+                )
+                .with_modifiers(modifiers),
+            ));
             self.application_event(
                 &event,
                 message_ctx,
@@ -392,12 +578,15 @@ impl UserInterface {
                 senders.clone(),
             )
         }
-        let event = ApplicationEvent::Mouse(MouseEvent::MouseUp(MouseEventData::new(
-            window_id,
-            0,
-            &self.mouse_position.unwrap(),
-            &self.mouse_position.unwrap(),
-        )));
+        let event = ApplicationEvent::Mouse(MouseEvent::MouseUp(
+            MouseEventData::new(
+                window_id,
+                0,
+                &self.mouse_position.unwrap(),
+                &self.mouse_position.unwrap(),
+            )
+            .with_modifiers(modifiers),
+        ));
         self.application_event(
             &event,
             message_ctx,
@@ -410,6 +599,7 @@ impl UserInterface {
     pub fn mouse_scroll(
         &mut self,
         window_id: WindowId,
+        modifiers: ModifiersState,
         scroll: (f32, f32),
         message_ctx: &mut ApplicationCtx,
         ui_state: &UIState,
@@ -423,6 +613,7 @@ impl UserInterface {
                 &self.mouse_position.unwrap(),
                 &self.mouse_position.unwrap(),
             )
+            .with_modifiers(modifiers)
             .with_scroll(scroll),
         ));
         self.application_event(&event, message_ctx, ui_state, event_response, senders)
@@ -439,9 +630,53 @@ impl UserInterface {
         let mut intercepted = Vec::new();
         let mut hit = None;
         self.hit_test(event.local_position(), &mut intercepted, &mut hit);
-        if let Some(hit) = hit {
+        if let MouseEvent::MouseScroll(data) = event {
+            // Scroll events go from the innermost element outwards, every element gets what the elements
+            // inside of it didn't consume.
+            let mut remaining = data.scroll();
+            for id in hit.iter().chain(intercepted.iter().rev()) {
+                if remaining.x == 0.0 && remaining.y == 0.0 {
+                    break;
+                }
+
+                let event =
+                    MouseEvent::MouseScroll(data.clone().with_scroll((remaining.x, remaining.y)));
+                let consumed = self.send_mouse_event(
+                    *id,
+                    &event,
+                    message_ctx,
+                    ui_state,
+                    event_response,
+                    senders.clone(),
+                );
+                remaining = remaining - consumed;
+            }
+
+            return;
+        }
+
+        // Pressing the mouse takes the focus away, unless one of the pressed elements asks for it
+        let focused = if let MouseEvent::MouseDown(_) = event {
+            self.focused.take()
+        } else {
+            self.focused
+        };
+
+        let mut targets: Vec<ElementId> = hit.iter().chain(intercepted.iter()).copied().collect();
+        // The elements that were pressed keep getting the drag and release, even when the mouse left them
+        if let MouseEvent::MouseDrag(_) | MouseEvent::MouseDragEnd(_) | MouseEvent::MouseUp(_) =
+            event
+        {
+            for id in &self.mouse_down_elements {
+                if !targets.contains(id) {
+                    targets.push(*id);
+                }
+            }
+        }
+
+        for id in &targets {
             self.send_mouse_event(
-                hit,
+                *id,
                 event,
                 message_ctx,
                 ui_state,
@@ -450,43 +685,65 @@ impl UserInterface {
             )
         }
 
-        for intercept in &intercepted {
-            self.send_mouse_event(
-                *intercept,
-                event,
-                message_ctx,
+        match event {
+            MouseEvent::MouseDown(_) => {
+                self.mouse_down_elements = targets;
+                if self.focused != focused {
+                    self.focus_changed(focused, false, senders.clone());
+                    self.focus_changed(self.focused, true, senders);
+                }
+            }
+            MouseEvent::MouseUp(_) => self.mouse_down_elements.clear(),
+            _ => {}
+        }
+    }
+
+    fn focus_changed(&mut self, id: Option<ElementId>, focused: bool, senders: Senders) {
+        // The element may have been removed by a rebuild since it got the focus
+        let Some(node) = id.and_then(|id| self.root_tree.get(id)) else {
+            return;
+        };
+
+        let state = node.data.state();
+        let mut ui_ctx = UIContext::new(id.unwrap(), state.as_deref(), &self.root_tree, senders);
+        node.data.widget().focus_changed(focused, &mut ui_ctx);
+    }
+
+    /// Sends the key event to the focused element.
+    pub fn key_event(
+        &mut self,
+        event: &KeyEvent,
+        message_ctx: &mut ApplicationCtx,
+        ui_state: &UIState,
+        senders: Senders,
+    ) {
+        let Some(id) = self.focused else {
+            return;
+        };
+        let Some(node) = self.root_tree.get(id) else {
+            return;
+        };
+
+        let state = node.data.state();
+        let mut event_ctx = EventCtx::new(UIEvent::Key(event), ui_state);
+        let mut ui_ctx = UIContext::new(id, state.as_deref(), &self.root_tree, senders.clone());
+        node.data
+            .widget()
+            .key_event(ui_state, &mut event_ctx, &mut ui_ctx, message_ctx);
+
+        let scroll_requests = ui_ctx.scroll_requests;
+        for message in ui_ctx.ui_messages {
+            self.send_internal_message(
+                message.receiver,
+                UIEvent::Internal(&message),
                 ui_state,
-                event_response,
                 senders.clone(),
             )
         }
 
-        // match event {
-        //     MouseEvent::MouseUp(_) => {
-        //         for mouse_down in self.mouse_down_elements.clone() {
-        //             self.send_mouse_event(mouse_down, event, message_ctx, ui_state, event_response)
-        //         }
-
-        //         self.mouse_down_elements.clear();
-        //     }
-        //     MouseEvent::MouseDown(_) => {
-        //         if let Some(hit) = hit {
-        //             self.mouse_down_elements.push(hit);
-        //         }
-        //         self.mouse_down_elements.extend(intercepted.into_iter())
-        //     }
-        //     MouseEvent::MouseDrag(_) => {
-        //         for mouse_down in self.mouse_down_elements.clone() {
-        //             self.send_mouse_event(mouse_down, event, message_ctx, ui_state, event_response)
-        //         }
-        //     }
-        //     MouseEvent::MouseDragEnd(_) => {
-        //         for mouse_down in self.mouse_down_elements.clone() {
-        //             self.send_mouse_event(mouse_down, event, message_ctx, ui_state, event_response)
-        //         }
-        //     }
-        //     _ => {}
-        // }
+        for rect in scroll_requests {
+            self.scroll_into_view(id, rect, ui_state, senders.clone())
+        }
     }
 
     pub fn animation_event(
@@ -524,7 +781,9 @@ impl UserInterface {
             ApplicationEvent::Mouse(mouse_event) => {
                 self.mouse_event(mouse_event, message_ctx, ui_state, event_response, senders);
             }
-            ApplicationEvent::Key(_) => (),
+            ApplicationEvent::Key(key_event) => {
+                self.key_event(key_event, message_ctx, ui_state, senders)
+            }
             ApplicationEvent::Resize(_) => (),
             ApplicationEvent::Focus(_) => (),
             ApplicationEvent::Animation(element_id, animation_event) => self.animation_event(
@@ -578,21 +837,18 @@ impl UserInterface {
         response
     }
 
-    /// Removes the node from the tree and from its parent then build a new subtree from the node's widget.
+    /// Takes the node and its subtree out of the tree then builds it again from the node's widget.
+    /// Children with a key keep their elements, the parent keeps the node in its place.
     pub fn rebuild_element(&mut self, id: ElementId, ui_state: &UIState) -> Rebuild {
         let parent = self.root_tree.find_parent(id);
-        let mut node = self.root_tree.remove_node(id);
-        node.children.clear();
-        let tree = WidgetTreeBuilder::new_with_root_node(node, id).build(ui_state);
+        let subtree = self.root_tree.take_subtree(id);
+        let tree = WidgetTreeBuilder::new_with_tree(subtree).build(ui_state);
 
         Rebuild { parent, id, tree }
     }
 
     fn merge_subtree(&mut self, parent: usize, tree: WidgetTree) {
-        self.root_tree.add_child(parent, tree.root_id());
-        for (id, node) in tree.consume_nodes() {
-            self.root_tree.add_node_with_id(id, node);
-        }
+        self.root_tree.merge_subtree(parent, tree);
     }
 
     pub fn merge_rebuild(
@@ -616,9 +872,10 @@ impl UserInterface {
         &self,
         id: ElementId,
         constraints: &BoxConstraints,
+        ui_state: &UIState,
     ) -> Option<Size> {
         if let Some(node) = self.root_tree.get(id) {
-            let size_ctx = SizeCtx::new(id, &self.root_tree);
+            let size_ctx = SizeCtx::new(id, &self.root_tree, ui_state, self.text_measurer.as_ref());
             node.data
                 .widget()
                 .calculate_size(&node.children, constraints, &size_ctx)
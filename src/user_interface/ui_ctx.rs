@@ -1,6 +1,11 @@
 use std::{any::Any, sync::Arc};
 
-use crate::{app::Senders, tree::ElementId, widget::ui_message::UIMessage};
+use crate::{
+    app::Senders,
+    geo::Rect,
+    tree::ElementId,
+    widget::{style::TextDirection, ui_message::UIMessage},
+};
 
 use super::widget_tree::WidgetTree;
 
@@ -10,6 +15,8 @@ pub struct UIContext<'a> {
     element_tree: &'a WidgetTree,
     senders: Senders,
     pub ui_messages: Vec<UIMessage>,
+    pub scroll_requests: Vec<Rect>,
+    pub focus_requested: bool,
 }
 
 impl<'a> UIContext<'a> {
@@ -25,6 +32,8 @@ impl<'a> UIContext<'a> {
             element_tree,
             senders,
             ui_messages: vec![],
+            scroll_requests: vec![],
+            focus_requested: false,
         }
     }
 
@@ -51,6 +60,30 @@ impl<'a> UIContext<'a> {
         self.element_tree[self.id].children[index]
     }
 
+    pub fn children(&self, id: ElementId) -> &[ElementId] {
+        &self.element_tree[id].children
+    }
+
+    /// The bounds of an element relative to its parent, as of the last layout.
+    pub fn local_bounds(&self, id: ElementId) -> Rect {
+        self.element_tree[id].local_bounds
+    }
+
+    pub fn text_direction(&self) -> TextDirection {
+        self.element_tree[self.id].data().text_direction()
+    }
+
+    /// Asks the enclosing scrollables to scroll until the rect, in local coordinates, is visible.
+    pub fn scroll_into_view(&mut self, rect: Rect) {
+        self.scroll_requests.push(rect);
+    }
+
+    /// Makes this element receive the key events, until another element asks for the focus or the
+    /// user presses the mouse outside of it. Only has an effect while handling a mouse event.
+    pub fn request_focus(&mut self) {
+        self.focus_requested = true;
+    }
+
     pub fn set_state<T>(&mut self, modify: impl Fn(&T) -> T + Send + 'static)
     where
         T: Any + Send + 'static,
@@ -1,4 +1,9 @@
-use crate::user_interface::ui_state::UIState;
+use std::borrow::Cow;
+
+use crate::{
+    localization::{current_locale, localize, LOCALE},
+    user_interface::ui_state::UIState,
+};
 
 #[derive(Clone)]
 pub struct Array {
@@ -17,22 +22,83 @@ impl ToString for Array {
     }
 }
 
+/// A message that is looked up by key in the catalog of the current locale.
+/// The arguments are available to the message as Fluent variables, for example `{ $count }`.
+#[derive(Clone)]
+pub struct Localized {
+    key: String,
+    args: Vec<(String, Value)>,
+}
+
+impl Localized {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with_arg(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.args.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn format(&self, ui_state: &UIState) -> String {
+        let args: Vec<(&str, Cow<Var>)> = self
+            .args
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.var(ui_state)))
+            .collect();
+        let args: Vec<(&str, &Var)> = args
+            .iter()
+            .map(|(name, var)| (*name, var.as_ref()))
+            .collect();
+
+        let locale = current_locale(ui_state);
+        localize(&locale, &self.key, &args)
+    }
+}
+
 #[derive(Clone)]
 pub enum Value {
     Binding(String),
     Const(Var),
+    Localized(Localized),
 }
 
 impl Value {
-    pub fn var<'this, 'ui>(&'this self, ui_state: &'ui UIState) -> &Var
+    /// The current value, localized values are formatted for the current locale.
+    pub fn var<'this, 'ui>(&'this self, ui_state: &'ui UIState) -> Cow<'this, Var>
     where
         'ui: 'this,
     {
         match self {
-            Value::Binding(binding) => &ui_state[binding],
-            Value::Const(var) => var,
+            Value::Binding(binding) => Cow::Borrowed(&ui_state[binding]),
+            Value::Const(var) => Cow::Borrowed(var),
+            Value::Localized(localized) => Cow::Owned(Var::String(localized.format(ui_state))),
         }
     }
+
+    /// The bindings the value depends on, widgets bind to these to update when the value changes.
+    pub fn binds(&self) -> Vec<String> {
+        match self {
+            Value::Binding(binding) => vec![binding.clone()],
+            Value::Const(_) => Vec::new(),
+            Value::Localized(localized) => std::iter::once(LOCALE.to_string())
+                .chain(localized.args.iter().flat_map(|(_, value)| value.binds()))
+                .collect(),
+        }
+    }
+}
+
+impl From<Localized> for Value {
+    fn from(localized: Localized) -> Self {
+        Value::Localized(localized)
+    }
 }
 
 impl<T> From<T> for Value
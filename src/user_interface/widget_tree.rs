@@ -1,10 +1,21 @@
-use crate::{tree::Tree, widget::Widget};
-use std::{any::Any, sync::Arc};
+use crate::{
+    geo::{Point, Rect},
+    tree::{ElementId, Tree},
+    widget::{style::TextDirection, BuildCtx, Children, Widget},
+};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 pub type WidgetTree = Tree<WidgetElement>;
 pub struct WidgetElement {
     pub widget: Box<dyn Widget>,
     widget_state: Option<Arc<dyn Any + Send>>,
+    visible_rect: Option<Rect>,
+    text_direction: TextDirection,
+    key: Option<usize>,
 }
 
 impl WidgetElement {
@@ -12,9 +23,22 @@ impl WidgetElement {
         Self {
             widget,
             widget_state: None,
+            visible_rect: None,
+            text_direction: TextDirection::default(),
+            key: None,
         }
     }
 
+    pub fn with_key(mut self, key: Option<usize>) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// The key the parent gave this element, see `BuildCtx::set_child_keys`.
+    pub fn key(&self) -> Option<usize> {
+        self.key
+    }
+
     pub fn widget(&self) -> &dyn Widget {
         self.widget.as_ref()
     }
@@ -32,4 +56,76 @@ impl WidgetElement {
     pub fn set_state(&mut self, state: Option<Arc<dyn Any + Send>>) {
         self.widget_state = state
     }
+
+    /// The part of this element that was visible in its closest enclosing viewport when it was built.
+    pub fn visible_rect(&self) -> Option<Rect> {
+        self.visible_rect
+    }
+
+    pub fn set_visible_rect(&mut self, visible_rect: Option<Rect>) {
+        self.visible_rect = visible_rect
+    }
+
+    /// The text direction this element was built with, inherited from its parent.
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction
+    }
+
+    pub fn set_text_direction(&mut self, text_direction: TextDirection) {
+        self.text_direction = text_direction
+    }
+}
+
+impl WidgetTree {
+    /// Replaces the children of the element with the widgets it built and returns their ids in
+    /// order, the children still have to be built. A child with the key of a previous child gets
+    /// the element of that child, the other previous children are removed with everything below them.
+    pub fn update_children(
+        &mut self,
+        id: ElementId,
+        children: Children,
+        build_ctx: &BuildCtx,
+    ) -> Vec<ElementId> {
+        let previous = std::mem::take(&mut self[id].children);
+        let mut keyed: HashMap<usize, ElementId> = previous
+            .iter()
+            .filter_map(|child| self[*child].data.key().map(|key| (key, *child)))
+            .collect();
+        let keys = build_ctx.child_keys();
+        let text_direction = build_ctx.child_text_direction();
+
+        let mut ids = Vec::with_capacity(children.len());
+        for (index, widget) in children.into_iter().enumerate() {
+            let key = keys.get(index).copied();
+            let (child_id, offset) = match key.and_then(|key| keyed.remove(&key)) {
+                Some(child_id) => {
+                    self[child_id].data.widget = widget;
+                    (child_id, self[child_id].local_bounds.position())
+                }
+                None => {
+                    // Until it is laid out, a new child is expected where the previous one at its index was
+                    let offset = previous
+                        .get(index)
+                        .filter(|_| key.is_none())
+                        .map(|previous| self[*previous].local_bounds.position())
+                        .unwrap_or(Point::new(0.0, 0.0));
+                    let element = WidgetElement::new(widget).with_key(key);
+                    (self.add_node(element), offset)
+                }
+            };
+
+            let element = &mut self[child_id].data;
+            element.set_visible_rect(build_ctx.child_visible_rect(offset));
+            element.set_text_direction(text_direction);
+            self.add_child(id, child_id);
+            ids.push(child_id);
+        }
+
+        let kept: HashSet<&ElementId> = ids.iter().collect();
+        for child in previous.iter().filter(|child| !kept.contains(child)) {
+            self.remove_node(*child);
+        }
+
+        ids
+    }
 }
@@ -25,6 +25,11 @@ impl WidgetTreeBuilder {
         }
     }
 
+    /// Builds on an existing tree, its elements are kept for children with the same key.
+    pub fn new_with_tree(tree: WidgetTree) -> Self {
+        Self { tree }
+    }
+
     pub fn new_with_root_id(root: Box<dyn Widget>, root_id: ElementId) -> Self {
         Self {
             tree: WidgetTree::new_with_root_id(WidgetElement::new(root), root_id),
@@ -37,11 +42,13 @@ impl WidgetTreeBuilder {
             node.data.set_state(node.data().widget().state(ui_state))
         }
 
-        let mut build_ctx = BuildCtx::new(id, node.data.state(), ui_state);
-        for child in node.data.widget().build(&mut build_ctx) {
-            let child_id = self.tree.add_node(WidgetElement::new(child));
+        let mut build_ctx = BuildCtx::new(id, node.data.state(), ui_state)
+            .with_size(node.local_bounds.size())
+            .with_visible_rect(node.data.visible_rect())
+            .with_text_direction(node.data.text_direction());
+        let children = node.data.widget().build(&mut build_ctx);
+        for child_id in self.tree.update_children(id, children, &build_ctx) {
             self.build_element(ui_state, child_id);
-            self.tree.add_child(id, child_id);
         }
     }
 
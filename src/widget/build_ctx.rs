@@ -2,16 +2,25 @@ use std::{any::Any, sync::Arc, time::Duration};
 
 use crate::{
     animation::{animation_request::AnimationRequest, AnimationId},
+    geo::{Point, Rect, Size},
     tree::ElementId,
     user_interface::{ui_state::UIState, value::Var},
 };
 
+use super::style::TextDirection;
+
 pub struct BuildCtx<'a> {
     pub id: ElementId,
     ui_state: &'a UIState,
     animation_requests: Vec<AnimationRequest>,
     widget_state: Option<Arc<dyn Any + Send>>,
     binds: Vec<String>,
+    size: Size,
+    visible_rect: Option<Rect>,
+    child_visible_rect: Option<Rect>,
+    text_direction: TextDirection,
+    child_text_direction: Option<TextDirection>,
+    child_keys: Vec<usize>,
 }
 
 impl<'a> BuildCtx<'a> {
@@ -26,9 +35,30 @@ impl<'a> BuildCtx<'a> {
             ui_state,
             animation_requests: Vec::new(),
             binds: Vec::new(),
+            size: Size::new(0.0, 0.0),
+            visible_rect: None,
+            child_visible_rect: None,
+            text_direction: TextDirection::default(),
+            child_text_direction: None,
+            child_keys: Vec::new(),
         }
     }
 
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_visible_rect(mut self, visible_rect: Option<Rect>) -> Self {
+        self.visible_rect = visible_rect;
+        self
+    }
+
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
     pub fn state<T: Any>(&self) -> Option<&T> {
         self.widget_state
             .as_ref()
@@ -61,4 +91,54 @@ impl<'a> BuildCtx<'a> {
     pub fn binds(&self) -> Vec<String> {
         self.binds.clone()
     }
+
+    /// The size of this element after its last layout. Zero if it hasn't been laid out yet.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// The part of this element that is visible in its closest enclosing viewport, in local coordinates.
+    /// Widgets that build their children lazily use this to only build what can be seen.
+    pub fn visible_rect(&self) -> Option<Rect> {
+        self.visible_rect
+    }
+
+    /// Overrides the visible rect that is passed on to the children, in their coordinates.
+    /// Used by widgets that scroll their content.
+    pub fn set_child_visible_rect(&mut self, rect: Rect) {
+        self.child_visible_rect = Some(rect)
+    }
+
+    /// The visible rect of a child at the offset in this element, in the coordinates of the child.
+    pub fn child_visible_rect(&self, child_offset: Point) -> Option<Rect> {
+        self.child_visible_rect.or_else(|| {
+            self.visible_rect
+                .map(|rect| rect.with_offset(Point::new(-child_offset.x, -child_offset.y)))
+        })
+    }
+
+    /// Identifies the children by a key, one for every child in the order they are built. A child
+    /// with the key of a child of the previous build keeps its element, with its state and its
+    /// bounds, and only gets the new widget. A key must keep referring to the same kind of widget.
+    pub fn set_child_keys(&mut self, keys: impl IntoIterator<Item = usize>) {
+        self.child_keys = keys.into_iter().collect();
+    }
+
+    pub fn child_keys(&self) -> &[usize] {
+        &self.child_keys
+    }
+
+    /// The text direction of this element, inherited from its parent or set for the window.
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction
+    }
+
+    /// Overrides the text direction for the children and everything below them.
+    pub fn set_child_text_direction(&mut self, text_direction: TextDirection) {
+        self.child_text_direction = Some(text_direction)
+    }
+
+    pub fn child_text_direction(&self) -> TextDirection {
+        self.child_text_direction.unwrap_or(self.text_direction)
+    }
 }
@@ -1,6 +1,6 @@
 use crate::geo::Size;
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct BoxConstraints {
     min_width: Option<f32>,
     min_height: Option<f32>,
@@ -57,9 +57,10 @@ impl BoxConstraints {
         self
     }
 
+    /// The constraints with the maximum size made smaller, it doesn't become negative.
     pub fn shrunk(&self, dw: f32, dh: f32) -> Self {
-        let width = self.max_width.map(|width| width - dw);
-        let height = self.max_height.map(|height| height - dh);
+        let width = self.max_width.map(|width| (width - dw).max(0.0));
+        let height = self.max_height.map(|height| (height - dh).max(0.0));
 
         Self {
             min_width: self.min_width,
@@ -69,6 +70,38 @@ impl BoxConstraints {
         }
     }
 
+    /// Returns the same maximum constraints without any minimum constraints.
+    pub fn loosened(&self) -> Self {
+        Self {
+            min_width: None,
+            min_height: None,
+            max_width: self.max_width,
+            max_height: self.max_height,
+        }
+    }
+
+    /// Returns `self` restricted to what `other` allows.
+    /// Constraints of `self` that fall outside of `other` are clamped to the bounds of `other`.
+    pub fn enforce(&self, other: &BoxConstraints) -> Self {
+        let clamp_width = |width: f32| clamp(width, other.min_width, other.max_width);
+        let clamp_height = |height: f32| clamp(height, other.min_height, other.max_height);
+
+        Self {
+            min_width: self.min_width.map(clamp_width).or(other.min_width),
+            min_height: self.min_height.map(clamp_height).or(other.min_height),
+            max_width: self.max_width.map(clamp_width).or(other.max_width),
+            max_height: self.max_height.map(clamp_height).or(other.max_height),
+        }
+    }
+
+    /// Returns the size closest to `size` that satisfies these constraints.
+    pub fn constrain(&self, size: Size) -> Size {
+        Size::new(
+            clamp(size.width, self.min_width, self.max_width),
+            clamp(size.height, self.min_height, self.max_height),
+        )
+    }
+
     pub fn min_width(&self) -> Option<f32> {
         self.min_width
     }
@@ -99,3 +132,8 @@ impl BoxConstraints {
         )
     }
 }
+
+fn clamp(value: f32, min: Option<f32>, max: Option<f32>) -> f32 {
+    let value = max.map(|max| value.min(max)).unwrap_or(value);
+    min.map(|min| value.max(min)).unwrap_or(value)
+}
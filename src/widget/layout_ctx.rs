@@ -1,20 +1,61 @@
 use crate::{
+    canvas::{
+        font::Font,
+        text_measurer::{TextMeasurer, TextMetrics},
+    },
     geo::{Point, Rect, Size},
     tree::ElementId,
-    user_interface::{ui_state::UIState, value::Var, widget_tree::WidgetTree},
+    user_interface::{
+        ui_state::UIState,
+        value::{Value, Var},
+        widget_tree::WidgetTree,
+    },
 };
 use std::collections::HashMap;
 
-use super::constraints::BoxConstraints;
+use super::{constraints::BoxConstraints, style::TextDirection};
 
 pub struct SizeCtx<'a> {
     id: ElementId,
     element_tree: &'a WidgetTree,
+    ui_state: &'a UIState,
+    text_measurer: &'a dyn TextMeasurer,
 }
 
 impl<'a> SizeCtx<'a> {
-    pub fn new(id: ElementId, element_tree: &'a WidgetTree) -> Self {
-        Self { id, element_tree }
+    pub fn new(
+        id: ElementId,
+        element_tree: &'a WidgetTree,
+        ui_state: &'a UIState,
+        text_measurer: &'a dyn TextMeasurer,
+    ) -> Self {
+        Self {
+            id,
+            element_tree,
+            ui_state,
+            text_measurer,
+        }
+    }
+
+    /// Measures the text of the value as a single line, `None` if it is bound to a binding that doesn't exist.
+    /// It is shaped with the direction the widget inherits.
+    pub fn measure_text(&self, text: &Value, font: &Font) -> Option<TextMetrics> {
+        let text_direction = self.text_direction();
+        measure_text(
+            self.text_measurer,
+            self.ui_state,
+            text,
+            font,
+            text_direction,
+        )
+    }
+
+    pub fn text_measurer(&self) -> &'a dyn TextMeasurer {
+        self.text_measurer
+    }
+
+    pub fn ui_state(&self) -> &'a UIState {
+        self.ui_state
     }
 
     pub fn state<T: 'static>(&self) -> Option<&T> {
@@ -22,10 +63,24 @@ impl<'a> SizeCtx<'a> {
     }
 
     pub fn preferred_size(&self, id: ElementId, constraints: &BoxConstraints) -> Option<Size> {
+        // The child measures with its own state and text direction.
+        let size_ctx = SizeCtx::new(id, self.element_tree, self.ui_state, self.text_measurer);
         let node = &self.element_tree[id];
         node.data()
             .widget()
-            .calculate_size(&node.children, constraints, self)
+            .calculate_size(&node.children, constraints, &size_ctx)
+    }
+
+    pub fn parent_data<T: 'static>(&self, id: ElementId) -> Option<&T> {
+        self.element_tree[id]
+            .data()
+            .widget()
+            .parent_data()
+            .and_then(|data| data.downcast_ref::<T>())
+    }
+
+    pub fn text_direction(&self) -> TextDirection {
+        self.element_tree[self.id].data().text_direction()
     }
 }
 
@@ -33,19 +88,54 @@ pub struct LayoutCtx<'a> {
     id: ElementId,
     element_tree: &'a WidgetTree,
     ui_state: &'a UIState,
+    text_measurer: &'a dyn TextMeasurer,
     bounds: HashMap<usize, Rect>,
+    dpi: f32,
 }
 
 impl<'a> LayoutCtx<'a> {
-    pub fn new(id: ElementId, element_tree: &'a WidgetTree, ui_state: &'a UIState) -> Self {
+    pub fn new(
+        id: ElementId,
+        element_tree: &'a WidgetTree,
+        ui_state: &'a UIState,
+        text_measurer: &'a dyn TextMeasurer,
+    ) -> Self {
         Self {
             id,
             element_tree,
             bounds: HashMap::new(),
             ui_state,
+            text_measurer,
+            dpi: 1.0,
         }
     }
 
+    pub fn with_dpi(mut self, dpi: f32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// The number of pixels per unit of the bounds in the window, widgets use it to prepare
+    /// images at the size they end up on the screen.
+    pub fn dpi(&self) -> f32 {
+        self.dpi
+    }
+
+    pub fn measure_text(&self, text: &Value, font: &Font) -> Option<TextMetrics> {
+        let text_direction = self.text_direction();
+        measure_text(
+            self.text_measurer,
+            self.ui_state,
+            text,
+            font,
+            text_direction,
+        )
+    }
+
+    pub fn text_measurer(&self) -> &'a dyn TextMeasurer {
+        self.text_measurer
+    }
+
     pub fn binding(&self, name: &str) -> Option<&Var> {
         self.ui_state.get(name)
     }
@@ -55,13 +145,21 @@ impl<'a> LayoutCtx<'a> {
     }
 
     pub fn preferred_size(&self, id: ElementId, constraints: &BoxConstraints) -> Option<Size> {
-        let size_ctx = SizeCtx::new(id, self.element_tree);
+        let size_ctx = SizeCtx::new(id, self.element_tree, self.ui_state, self.text_measurer);
         let node = &self.element_tree[id];
         node.data()
             .widget()
             .calculate_size(&node.children, constraints, &size_ctx)
     }
 
+    pub fn parent_data<T: 'static>(&self, id: ElementId) -> Option<&T> {
+        self.element_tree[id]
+            .data()
+            .widget()
+            .parent_data()
+            .and_then(|data| data.downcast_ref::<T>())
+    }
+
     pub fn set_child_bounds(&mut self, id: ElementId, rect: Rect) {
         self.bounds.insert(id, rect);
     }
@@ -78,4 +176,24 @@ impl<'a> LayoutCtx<'a> {
     pub fn state<T: 'static>(&self) -> Option<&T> {
         self.element_tree[self.id].data.widget_state()
     }
+
+    /// The text direction of the element that is laid out, row-like layouts are mirrored for right to left.
+    pub fn text_direction(&self) -> TextDirection {
+        self.element_tree[self.id].data().text_direction()
+    }
+}
+
+fn measure_text(
+    text_measurer: &dyn TextMeasurer,
+    ui_state: &UIState,
+    text: &Value,
+    font: &Font,
+    text_direction: TextDirection,
+) -> Option<TextMetrics> {
+    let text = match text {
+        Value::Binding(binding) => ui_state.get(binding)?.to_string(),
+        text => text.var(ui_state).to_string(),
+    };
+
+    Some(text_measurer.measure(&text, font, text_direction))
 }
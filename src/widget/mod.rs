@@ -11,12 +11,17 @@ pub use layout_ctx::SizeCtx;
 
 use crate::user_interface::ui_ctx::UIContext;
 use crate::user_interface::ui_state::UIState;
-use crate::{event_context::EventCtx, geo::Size, painter::Painter};
+use crate::{
+    event_context::EventCtx,
+    geo::{Point, Rect, Size, Transform},
+    painter::Painter,
+};
 use std::rc::Rc;
 use std::{any::Any, sync::Arc};
 
 use self::constraints::BoxConstraints;
 use self::message_context::ApplicationCtx;
+use self::style::TextDirection;
 
 pub type Child = Rc<dyn Fn(&UIState) -> Box<dyn Widget> + 'static>;
 pub type Children = Vec<Box<dyn Widget>>;
@@ -64,6 +69,18 @@ pub trait Widget {
     ) {
     }
 
+    /// Called with the key events while this widget has the focus, see `UIContext::request_focus`.
+    fn key_event(
+        &self,
+        _ui_state: &UIState,
+        _event_ctx: &mut EventCtx,
+        _ui_ctx: &mut UIContext,
+        _message_ctx: &mut ApplicationCtx,
+    ) {
+    }
+
+    fn focus_changed(&self, focused: bool, ui_ctx: &mut UIContext) {}
+
     fn animation_event(
         &self,
         event_context: &mut EventCtx,
@@ -81,6 +98,29 @@ pub trait Widget {
     fn intercept_mouse_events(&self) -> bool {
         false
     }
+
+    /// The transform, in local coordinates, that the painter applies to the children. Hit testing and
+    /// the local positions of mouse events map the pointer through its inverse.
+    fn transform(&self, size: Size, text_direction: TextDirection) -> Option<Transform> {
+        None
+    }
+
+    /// Whether the position, in local coordinates, is part of this widget. It is only asked for
+    /// positions inside of the bounds, returning false also skips the children, like a clip does.
+    fn hit_test(&self, position: &Point, size: Size) -> bool {
+        true
+    }
+
+    /// Data that this widget exposes to the layout of its parent, for example its position in a `Stack`.
+    fn parent_data(&self) -> Option<&dyn Any> {
+        None
+    }
+
+    /// Called on the ancestors of an element that wants the rect, in the local coordinates of this widget,
+    /// to be visible. Returns how far the rect moved, so the next ancestor can continue from there.
+    fn scroll_into_view(&self, rect: Rect, ui_ctx: &mut UIContext) -> Point {
+        Point::default()
+    }
 }
 
 impl<T> From<T> for Box<dyn Widget>
@@ -1,9 +1,31 @@
+use crate::geo::{Point, Size};
+
+/// The direction text and horizontal layouts flow in.
+/// It is set for a window and can be overridden for a subtree with `Directionality`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    pub fn is_right_to_left(&self) -> bool {
+        *self == TextDirection::RightToLeft
+    }
+}
+
+/// Insets on every edge. `start` and `end` are added to the left or right edge depending on
+/// the text direction, use `resolve` to get the insets on the physical edges.
 #[derive(Default, Clone, Copy)]
 pub struct Insets {
     pub left: f32,
     pub right: f32,
     pub top: f32,
     pub bottom: f32,
+    pub start: f32,
+    pub end: f32,
 }
 
 impl Insets {
@@ -13,24 +35,171 @@ impl Insets {
             right,
             top,
             bottom,
+            ..Default::default()
         }
     }
 
+    pub fn uniform(inset: f32) -> Self {
+        Self::all(inset, inset, inset, inset)
+    }
+
     pub fn horizontal(left: f32, right: f32) -> Self {
-        Self {
-            left,
-            right,
-            top: 0.0,
-            bottom: 0.0,
-        }
+        Self::all(left, right, 0.0, 0.0)
     }
 
     pub fn vertical(top: f32, bottom: f32) -> Self {
+        Self::all(0.0, 0.0, top, bottom)
+    }
+
+    /// Insets that follow the text direction, start is the left edge for left to right text.
+    pub fn directional(start: f32, end: f32, top: f32, bottom: f32) -> Self {
         Self {
-            left: 0.0,
-            right: 0.0,
             top,
             bottom,
+            start,
+            end,
+            ..Default::default()
+        }
+    }
+
+    /// The insets on the left, right, top and bottom edge for the text direction.
+    pub fn resolve(&self, direction: TextDirection) -> Self {
+        let (left, right) = match direction {
+            TextDirection::LeftToRight => (self.start, self.end),
+            TextDirection::RightToLeft => (self.end, self.start),
+        };
+
+        Self::all(self.left + left, self.right + right, self.top, self.bottom)
+    }
+}
+
+/// Fractional alignment of a child within its parent.
+/// (-1.0, -1.0) is the top left corner, (0.0, 0.0) the center and (1.0, 1.0) the bottom right corner.
+/// For directional alignments x goes from the start to the end edge instead, see `resolve`.
+#[derive(Clone, Copy)]
+pub struct Alignment {
+    pub x: f32,
+    pub y: f32,
+    directional: bool,
+}
+
+impl Alignment {
+    pub const TOP_LEFT: Self = Self::new(-1.0, -1.0);
+    pub const TOP_CENTER: Self = Self::new(0.0, -1.0);
+    pub const TOP_RIGHT: Self = Self::new(1.0, -1.0);
+    pub const CENTER_LEFT: Self = Self::new(-1.0, 0.0);
+    pub const CENTER: Self = Self::new(0.0, 0.0);
+    pub const CENTER_RIGHT: Self = Self::new(1.0, 0.0);
+    pub const BOTTOM_LEFT: Self = Self::new(-1.0, 1.0);
+    pub const BOTTOM_CENTER: Self = Self::new(0.0, 1.0);
+    pub const BOTTOM_RIGHT: Self = Self::new(1.0, 1.0);
+    pub const TOP_START: Self = Self::new_directional(-1.0, -1.0);
+    pub const TOP_END: Self = Self::new_directional(1.0, -1.0);
+    pub const CENTER_START: Self = Self::new_directional(-1.0, 0.0);
+    pub const CENTER_END: Self = Self::new_directional(1.0, 0.0);
+    pub const BOTTOM_START: Self = Self::new_directional(-1.0, 1.0);
+    pub const BOTTOM_END: Self = Self::new_directional(1.0, 1.0);
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self {
+            x,
+            y,
+            directional: false,
+        }
+    }
+
+    /// An alignment where x = -1.0 is the start edge and x = 1.0 the end edge.
+    pub const fn new_directional(x: f32, y: f32) -> Self {
+        Self {
+            x,
+            y,
+            directional: true,
+        }
+    }
+
+    /// The alignment on the physical edges for the text direction.
+    pub fn resolve(&self, direction: TextDirection) -> Self {
+        if self.directional && direction.is_right_to_left() {
+            Self::new(-self.x, self.y)
+        } else {
+            Self::new(self.x, self.y)
+        }
+    }
+
+    /// Returns the position of a child with the given size inside a parent with the given size.
+    /// Directional alignments have to be resolved first.
+    pub fn position(&self, child: Size, parent: Size) -> Point {
+        let free_width = parent.width - child.width;
+        let free_height = parent.height - child.height;
+        Point::new(
+            free_width * (self.x + 1.0) / 2.0,
+            free_height * (self.y + 1.0) / 2.0,
+        )
+    }
+}
+
+impl Default for Alignment {
+    fn default() -> Self {
+        Self::TOP_LEFT
+    }
+}
+
+/// Horizontal alignment of children and text. `Start` and `End` follow the text direction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum HorizontalAlignment {
+    Left,
+    #[default]
+    Center,
+    Right,
+    Start,
+    End,
+}
+
+impl HorizontalAlignment {
+    /// The alignment on the physical edges for the text direction.
+    pub fn resolve(&self, direction: TextDirection) -> Self {
+        match (self, direction) {
+            (HorizontalAlignment::Start, TextDirection::LeftToRight)
+            | (HorizontalAlignment::End, TextDirection::RightToLeft) => HorizontalAlignment::Left,
+            (HorizontalAlignment::Start, TextDirection::RightToLeft)
+            | (HorizontalAlignment::End, TextDirection::LeftToRight) => HorizontalAlignment::Right,
+            (alignment, _) => *alignment,
+        }
+    }
+}
+
+/// Vertical alignment of children and text.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum VerticalAlignment {
+    /// For text, the top of the tallest glyphs at the top of the rect.
+    Top,
+    /// For text, capital letters centered in the rect, which looks centered for most text.
+    #[default]
+    Center,
+    /// For text, the baseline at the ascent of the font from the top of the rect, so text in the
+    /// same font shares its baseline even when fallback fonts make some of it taller.
+    /// Anything else is aligned to the top.
+    Baseline,
+    /// For text, the bottom of the lowest descenders at the bottom of the rect.
+    Bottom,
+}
+
+impl From<(HorizontalAlignment, VerticalAlignment)> for Alignment {
+    fn from((horizontal, vertical): (HorizontalAlignment, VerticalAlignment)) -> Self {
+        let y = match vertical {
+            VerticalAlignment::Top | VerticalAlignment::Baseline => -1.0,
+            VerticalAlignment::Center => 0.0,
+            VerticalAlignment::Bottom => 1.0,
+        };
+
+        match horizontal {
+            HorizontalAlignment::Left => Self::new(-1.0, y),
+            HorizontalAlignment::Center => Self::new(0.0, y),
+            HorizontalAlignment::Right => Self::new(1.0, y),
+            HorizontalAlignment::Start => Self::new_directional(-1.0, y),
+            HorizontalAlignment::End => Self::new_directional(1.0, y),
         }
     }
 }
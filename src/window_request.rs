@@ -1,4 +1,7 @@
-use crate::{user_interface::ui_state::UIState, widget::Widget};
+use crate::{
+    user_interface::ui_state::UIState,
+    widget::{style::TextDirection, Widget},
+};
 
 type UIBuilder = dyn Fn(&UIState) -> Box<dyn Widget>;
 
@@ -6,6 +9,7 @@ pub struct WindowRequest {
     pub width: u32,
     pub height: u32,
     pub title: Option<String>,
+    pub text_direction: TextDirection,
     builder: Option<Box<UIBuilder>>,
 }
 
@@ -15,6 +19,7 @@ impl WindowRequest {
             width,
             height,
             title: None,
+            text_direction: TextDirection::LeftToRight,
             builder: None,
         }
     }
@@ -24,6 +29,12 @@ impl WindowRequest {
         self
     }
 
+    /// The direction of the text and layouts in the window, for example right to left for an Arabic locale.
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
     pub fn with_ui<F: 'static>(mut self, builder: F) -> Self
     where
         F: Fn(&UIState) -> Box<dyn Widget>,